@@ -15,14 +15,69 @@ pub const ARBITER_SEED: &[u8] = b"arbiter";
 #[constant]
 pub const FEE_COLLECTOR_SEED: &[u8] = b"fee_collector";
 
+#[constant]
+pub const VAULT_SEED: &[u8] = b"vault";
+
+#[constant]
+pub const EVIDENCE_SEED: &[u8] = b"evidence";
+
+#[constant]
+pub const REPUTATION_AUTHORITY_SEED: &[u8] = b"reputation_authority";
+
+#[constant]
+pub const DISPUTE_VOTE_SEED: &[u8] = b"dispute_vote";
+
+#[constant]
+pub const STAKE_VAULT_SEED: &[u8] = b"stake_vault";
+
 // Minimum escrow amount in lamports (0.01 SOL)
 pub const MIN_ESCROW_AMOUNT: u64 = 10_000_000;
 
 // Maximum escrow amount in lamports (1000 SOL)
 pub const MAX_ESCROW_AMOUNT: u64 = 1_000_000_000_000;
 
-// Dispute window in seconds (7 days)
-pub const DISPUTE_WINDOW: i64 = 7 * 24 * 60 * 60;
+// Maximum number of milestones per escrow
+pub const MAX_MILESTONES: usize = 10;
+
+// Maximum number of arbiters buyer and seller can assign to an escrow
+pub const MAX_ESCROW_ARBITERS: usize = 3;
+
+// Maximum length of an evidence URI
+pub const MAX_EVIDENCE_URI_LEN: usize = 200;
+
+// Maximum length of a manual reputation adjustment reason
+pub const MAX_REPUTATION_REASON_LEN: usize = 100;
+
+// Maximum number of reputation fee tiers in the config
+pub const MAX_FEE_TIERS: usize = 5;
+
+// Minimum and maximum number of arbiters on a dispute panel
+pub const MIN_PANEL_SIZE: usize = 3;
+pub const MAX_PANEL_SIZE: usize = 5;
+
+// Default panel voting period in seconds (3 days)
+pub const DEFAULT_VOTING_PERIOD: u32 = 3 * 24 * 60 * 60;
+
+// Default window for appealing a ruling in seconds (2 days)
+pub const DEFAULT_APPEAL_WINDOW: u32 = 2 * 24 * 60 * 60;
+
+// Default appeal bond in lamports (0.1 SOL)
+pub const DEFAULT_APPEAL_BOND: u64 = 100_000_000;
+
+// Default stake an arbiter must hold to rule on disputes in lamports (1 SOL)
+pub const DEFAULT_MIN_ARBITER_STAKE: u64 = 1_000_000_000;
+
+// Default wait between requesting an unstake and withdrawing in seconds (7 days)
+pub const DEFAULT_STAKE_COOLDOWN: u32 = 7 * 24 * 60 * 60;
+
+// Default stake slashed when an arbiter's ruling is overturned on appeal in lamports (0.1 SOL)
+pub const DEFAULT_OVERTURN_SLASH: u64 = 100_000_000;
+
+// Maximum length of an arbiter slashing reason
+pub const MAX_SLASH_REASON_LEN: usize = 100;
+
+// Default dispute window after acceptance in seconds (7 days)
+pub const DEFAULT_DISPUTE_WINDOW: u32 = 7 * 24 * 60 * 60;
 
-// Timeout period in seconds (30 days)
-pub const TIMEOUT_PERIOD: i64 = 30 * 24 * 60 * 60;
\ No newline at end of file
+// Default time after acceptance before the seller can claim in seconds (30 days)
+pub const DEFAULT_ESCROW_TIMEOUT: u32 = 30 * 24 * 60 * 60;
\ No newline at end of file
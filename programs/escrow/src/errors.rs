@@ -20,8 +20,88 @@ pub enum EscrowError {
     NotSeller,
     #[msg("Arithmetic overflow")]
     Overflow,
-    #[msg("Arbiter is not authorized or has been deactivated")]
+    #[msg("Arbiter is not authorized, has been deactivated or lacks the minimum stake")]
     UnauthorizedArbiter,
     #[msg("Invalid fee collector account")]
     InvalidFeeCollector,
+    #[msg("Token accounts are required for token escrows")]
+    MissingTokenAccounts,
+    #[msg("Token mint does not match the escrow mint")]
+    InvalidMint,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("Escrow timeout period has not elapsed")]
+    TimeoutNotReached,
+    #[msg("Milestones must be non-zero, at most MAX_MILESTONES and sum to the escrow amount")]
+    InvalidMilestones,
+    #[msg("Split share cannot exceed 10000 basis points")]
+    InvalidSplit,
+    #[msg("Arbiters must be unique, at most MAX_ESCROW_ARBITERS and not a party to the escrow")]
+    InvalidArbiters,
+    #[msg("Arbiter is not assigned to this escrow")]
+    ArbiterNotAssigned,
+    #[msg("Evidence URI exceeds the maximum length")]
+    EvidenceUriTooLong,
+    #[msg("Reputation adjustment reason is empty or too long")]
+    InvalidReason,
+    #[msg("New admin must be a valid key other than the current admin")]
+    InvalidAdmin,
+    #[msg("No admin transfer is pending")]
+    NoPendingAdminTransfer,
+    #[msg("Creating new escrows is paused")]
+    ProgramPaused,
+    #[msg("Program is in emergency mode, only cancellations are allowed")]
+    EmergencyMode,
+    #[msg("Fee tiers exceed MAX_FEE_TIERS or have an invalid rate")]
+    InvalidFeeTiers,
+    #[msg("Escrow dispute is not decided by a panel")]
+    NotPanelDispute,
+    #[msg("Arbiter has already voted on this dispute")]
+    AlreadyVoted,
+    #[msg("Every panel member has already voted")]
+    PanelFull,
+    #[msg("Panel has not reached a decision yet")]
+    VotingIncomplete,
+    #[msg("Resolution does not match the panel's decision")]
+    ResolutionMismatch,
+    #[msg("Panel arbiter accounts are missing or out of order")]
+    InvalidPanelAccounts,
+    #[msg("Appeal window has closed")]
+    AppealWindowClosed,
+    #[msg("Ruling cannot be executed while the appeal window is open")]
+    AppealWindowOpen,
+    #[msg("Only a party the ruling went against can appeal")]
+    NotLosingParty,
+    #[msg("Ruling has already been appealed")]
+    AlreadyAppealed,
+    #[msg("No other assigned arbiter is available to hear an appeal")]
+    NoAppealArbiter,
+    #[msg("Appeals must be decided by an arbiter who did not rule on the dispute")]
+    AppealArbiterConflict,
+    #[msg("Dispute has an appeal pending")]
+    AppealPending,
+    #[msg("Arbiter did not make the pending ruling")]
+    NotRulingArbiter,
+    #[msg("Arbiter has no stake")]
+    NoStake,
+    #[msg("Arbiter has not requested to unstake")]
+    UnstakeNotRequested,
+    #[msg("Stake cooldown has not elapsed")]
+    StakeCooldownActive,
+    #[msg("Stake vault does not belong to the arbiter")]
+    InvalidStakeVault,
+    #[msg("Accounts of the appealed arbiter are required to settle the appeal")]
+    MissingSlashAccounts,
+    #[msg("Arbiter is active")]
+    ArbiterActive,
+    #[msg("Arbiter must be removed, unstaked and have no pending rulings before closing")]
+    ArbiterNotClosable,
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Arbiter has rulings that have not been executed yet")]
+    PendingRulings,
+    #[msg("Panel voting period has ended")]
+    VotingClosed,
+    #[msg("Token-2022 mint has an extension escrows don't support, such as a transfer fee or hook")]
+    UnsupportedMint,
 }
\ No newline at end of file
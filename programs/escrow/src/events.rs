@@ -1,11 +1,24 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{DisputeReason, DisputeResolution, EvidenceCategory, FeeTier, Reputation, ReputationUpdate};
+
 #[event]
 pub struct EscrowCreated {
     pub escrow: Pubkey,
     pub buyer: Pubkey,
     pub seller: Pubkey,
+    pub escrow_id: u64,
     pub amount: u64,
+    pub milestone_count: u8,
+    pub arbiters: Vec<Pubkey>,
+    pub mint: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowAccepted {
+    pub escrow: Pubkey,
+    pub seller: Pubkey,
     pub timestamp: i64,
 }
 
@@ -13,6 +26,7 @@ pub struct EscrowCreated {
 pub struct FundsReleased {
     pub escrow: Pubkey,
     pub seller: Pubkey,
+    pub milestone_index: u8,
     pub amount: u64,
     pub fee_amount: u64,
     pub timestamp: i64,
@@ -26,11 +40,28 @@ pub struct EscrowCancelled {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct EscrowClosed {
+    pub escrow: Pubkey,
+    pub closed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EscrowTimedOut {
+    pub escrow: Pubkey,
+    pub claimed_by: Pubkey,
+    pub amount: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RefundIssued {
     pub escrow: Pubkey,
     pub buyer: Pubkey,
     pub amount: u64,
+    pub fee_amount: u64,
     pub reason: String,
     pub timestamp: i64,
 }
@@ -39,6 +70,18 @@ pub struct RefundIssued {
 pub struct DisputeRaised {
     pub escrow: Pubkey,
     pub raised_by: Pubkey,
+    pub reason: DisputeReason,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub escrow: Pubkey,
+    pub evidence: Pubkey,
+    pub submitted_by: Pubkey,
+    pub index: u16,
+    pub category: EvidenceCategory,
+    pub content_hash: [u8; 32],
     pub timestamp: i64,
 }
 
@@ -46,7 +89,11 @@ pub struct DisputeRaised {
 pub struct DisputeResolved {
     pub escrow: Pubkey,
     pub arbiter: Pubkey,
-    pub resolution: String,
+    pub resolution: DisputeResolution,
+    pub buyer_amount: u64,
+    pub seller_amount: u64,
+    pub arbiter_fee: u64,
+    pub platform_fee: u64,
     pub timestamp: i64,
 }
 
@@ -55,4 +102,216 @@ pub struct ReputationUpdated {
     pub user: Pubkey,
     pub successful_trades: u64,
     pub failed_trades: u64,
-}
\ No newline at end of file
+    pub total_volume: u64,
+    pub disputed_volume: u64,
+    pub disputes_raised: u64,
+    pub disputes_lost: u64,
+    pub last_activity_at: i64,
+}
+
+impl ReputationUpdated {
+    pub fn new(reputation: &Reputation) -> Self {
+        Self {
+            user: reputation.user,
+            successful_trades: reputation.successful_trades,
+            failed_trades: reputation.failed_trades,
+            total_volume: reputation.total_volume,
+            disputed_volume: reputation.disputed_volume,
+            disputes_raised: reputation.disputes_raised,
+            disputes_lost: reputation.disputes_lost,
+            last_activity_at: reputation.last_activity_at,
+        }
+    }
+}
+
+#[event]
+pub struct ReputationAdjusted {
+    pub user: Pubkey,
+    pub authority: Pubkey,
+    pub update: ReputationUpdate,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationAuthorityAdded {
+    pub authority: Pubkey,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationAuthorityRemoved {
+    pub authority: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ConfigUpdated {
+    pub admin: Pubkey,
+    pub parameter: String,
+    pub old_value: u64,
+    pub new_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferProposed {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferAccepted {
+    pub previous_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AdminTransferCancelled {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PauseUpdated {
+    pub authority: Pubkey,
+    pub escrows_paused: bool,
+    pub emergency_mode: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GuardianUpdated {
+    pub admin: Pubkey,
+    pub old_guardian: Pubkey,
+    pub new_guardian: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub admin: Pubkey,
+    pub fee_tiers: Vec<FeeTier>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeVoteCast {
+    pub escrow: Pubkey,
+    pub arbiter: Pubkey,
+    pub resolution: DisputeResolution,
+    pub votes_for: u8,
+    pub votes_cast: u8,
+    pub panel_size: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbiterAdded {
+    pub arbiter: Pubkey,
+    pub added_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbiterRemoved {
+    pub arbiter: Pubkey,
+    pub removed_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbiterReactivated {
+    pub arbiter: Pubkey,
+    pub reactivated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbiterClosed {
+    pub arbiter: Pubkey,
+    pub closed_by: Pubkey,
+    pub disputes_resolved: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeDeposited {
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub total_stake: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UnstakeRequested {
+    pub arbiter: Pubkey,
+    pub stake: u64,
+    pub withdrawable_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub arbiter: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ArbiterSlashed {
+    pub arbiter: Pubkey,
+    pub authority: Pubkey,  // Admin, or the escrow whose appeal overturned the ruling
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub remaining_stake: u64,
+    pub reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeRuled {
+    pub escrow: Pubkey,
+    pub arbiter: Pubkey,
+    pub resolution: DisputeResolution,
+    pub is_appeal: bool,
+    pub appeal_deadline: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RulingAppealed {
+    pub escrow: Pubkey,
+    pub appellant: Pubkey,
+    pub resolution: DisputeResolution,
+    pub bond: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppealDecided {
+    pub escrow: Pubkey,
+    pub appellant: Pubkey,
+    pub original_resolution: DisputeResolution,
+    pub resolution: DisputeResolution,
+    pub overturned: bool,
+    pub bond: u64,
+    pub bond_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DisputeVoteTallied {
+    pub escrow: Pubkey,
+    pub resolution: DisputeResolution,
+    pub votes_for: u8,
+    pub votes_cast: u8,
+    pub panel_size: u8,
+    pub deadline_passed: bool,
+    pub timestamp: i64,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::CONFIG_SEED,
+    errors::EscrowError,
+    events::{AdminTransferAccepted, GuardianUpdated},
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The proposed admin, signing proves control of the new key
+    pub new_admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let previous_admin = config.admin;
+
+    let clock = Clock::get()?;
+
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = None;
+
+    emit!(AdminTransferAccepted {
+        previous_admin,
+        new_admin: config.admin,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // A guardian still on the old key moves with the admin, so a lost or compromised
+    // key can't keep pausing the program
+    if config.guardian == previous_admin {
+        config.guardian = config.admin;
+
+        emit!(GuardianUpdated {
+            admin: config.admin,
+            old_guardian: previous_admin,
+            new_guardian: config.guardian,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!("Admin transferred from {} to {}", previous_admin, config.admin);
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::EscrowAccepted,
+    state::{Config, Escrow, EscrowStatus},
+};
+
+#[derive(Accounts)]
+pub struct AcceptEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        has_one = seller @ EscrowError::NotSeller,
+        constraint = escrow.can_accept() @ EscrowError::InvalidState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// The seller agreeing to the escrow terms, including its assigned arbiters
+    pub seller: Signer<'info>,
+
+    /// CHECK: Used for PDA derivation
+    pub buyer: AccountInfo<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.escrows_paused @ EscrowError::ProgramPaused,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<AcceptEscrow>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let clock = Clock::get()?;
+
+    // Seller accepts, the escrow becomes binding and can no longer be cancelled
+    escrow.status = EscrowStatus::Active;
+    escrow.accepted_at = clock.unix_timestamp;
+
+    emit!(EscrowAccepted {
+        escrow: escrow.key(),
+        seller: ctx.accounts.seller.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Escrow accepted by seller {}", ctx.accounts.seller.key());
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::{ARBITER_SEED, CONFIG_SEED},
+    constants::{ARBITER_SEED, CONFIG_SEED, STAKE_VAULT_SEED},
     errors::EscrowError,
+    events::ArbiterAdded,
     state::{Arbiter, Config},
 };
 
@@ -24,6 +25,14 @@ pub struct AddArbiter<'info> {
     )]
     pub arbiter_account: Account<'info, Arbiter>,
 
+    /// Stake vault PDA that will hold the arbiter's stake
+    /// CHECK: PDA will be validated by seeds constraint
+    #[account(
+        seeds = [STAKE_VAULT_SEED, arbiter.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: AccountInfo<'info>,
+
     /// The arbiter being added
     /// CHECK: Can be any valid account
     pub arbiter: AccountInfo<'info>,
@@ -41,11 +50,19 @@ pub fn handler(ctx: Context<AddArbiter>) -> Result<()> {
     let clock = Clock::get()?;
 
     // Initialize arbiter account
-    arbiter_account.arbiter = arbiter.key();
-    arbiter_account.added_by = admin.key();
-    arbiter_account.added_at = clock.unix_timestamp;
-    arbiter_account.is_active = true;
-    arbiter_account.bump = ctx.bumps.arbiter_account;
+    arbiter_account.set_inner(Arbiter::new(
+        arbiter.key(),
+        admin.key(),
+        clock.unix_timestamp,
+        ctx.bumps.arbiter_account,
+        ctx.bumps.stake_vault,
+    ));
+
+    emit!(ArbiterAdded {
+        arbiter: arbiter.key(),
+        added_by: admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
 
     msg!("Arbiter added: {}", arbiter.key());
     msg!("Added by admin: {}", admin.key());
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, REPUTATION_AUTHORITY_SEED},
+    errors::EscrowError,
+    events::ReputationAuthorityAdded,
+    state::{Config, ReputationAuthority},
+};
+
+#[derive(Accounts)]
+pub struct AddReputationAuthority<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ReputationAuthority::LEN,
+        seeds = [REPUTATION_AUTHORITY_SEED, authority.key().as_ref()],
+        bump
+    )]
+    pub reputation_authority: Account<'info, ReputationAuthority>,
+
+    /// The authority being approved, typically a PDA of a trusted program
+    /// CHECK: Can be any valid account
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AddReputationAuthority>) -> Result<()> {
+    let reputation_authority = &mut ctx.accounts.reputation_authority;
+    let authority = &ctx.accounts.authority;
+    let admin = &ctx.accounts.admin;
+    let clock = Clock::get()?;
+
+    // Initialize reputation authority account
+    reputation_authority.authority = authority.key();
+    reputation_authority.added_by = admin.key();
+    reputation_authority.added_at = clock.unix_timestamp;
+    reputation_authority.bump = ctx.bumps.reputation_authority;
+
+    emit!(ReputationAuthorityAdded {
+        authority: authority.key(),
+        added_by: admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Reputation authority added: {}", authority.key());
+    msg!("Added by admin: {}", admin.key());
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::RulingAppealed,
+    state::{Appeal, Config, DisputeVote, Escrow, EscrowStatus},
+};
+
+#[derive(Accounts)]
+pub struct AppealRuling<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Ruled @ EscrowError::InvalidState,
+        constraint = escrow.appeal.is_none() @ EscrowError::AlreadyAppealed,
+        constraint = escrow.is_losing_party(&appellant.key()) @ EscrowError::NotLosingParty,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// The party the ruling went against, posts the appeal bond
+    #[account(mut)]
+    pub appellant: Signer<'info>,
+
+    /// Panel votes (panel disputes only), the panel can't hear its own appeal
+    #[account(
+        seeds = [DISPUTE_VOTE_SEED, escrow.key().as_ref()],
+        bump = dispute_vote.bump,
+    )]
+    pub dispute_vote: Option<Account<'info, DisputeVote>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Used for PDA derivation
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Used for PDA derivation
+    pub seller: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Appeals a pending ruling. The dispute goes back to `Disputed` to be ruled on again by an
+/// assigned arbiter who had no part in the appealed ruling.
+pub fn handler(ctx: Context<AppealRuling>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let appellant = &ctx.accounts.appellant;
+    let clock = Clock::get()?;
+
+    require!(
+        escrow.can_appeal(clock.unix_timestamp),
+        EscrowError::AppealWindowClosed
+    );
+
+    let original_arbiter = escrow.ruled_by.ok_or(EscrowError::InvalidState)?;
+    let original_resolution = escrow.pending_resolution.ok_or(EscrowError::InvalidState)?;
+
+    // Somebody other than the original arbiter, or any panel member, must be left to decide
+    let mut excluded = vec![original_arbiter];
+    if escrow.is_panel_dispute() {
+        let dispute_vote = ctx
+            .accounts
+            .dispute_vote
+            .as_ref()
+            .ok_or(EscrowError::InvalidPanelAccounts)?;
+        excluded.extend(dispute_vote.votes.iter().map(|vote| vote.arbiter));
+    }
+    require!(
+        escrow.has_appeal_arbiter(&excluded),
+        EscrowError::NoAppealArbiter
+    );
+
+    // Hold the bond in the escrow account until the appeal is decided
+    let bond = ctx.accounts.config.appeal_bond;
+    if bond > 0 {
+        let transfer_accounts = Transfer {
+            from: appellant.to_account_info(),
+            to: escrow.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+        );
+        transfer(cpi_context, bond)?;
+    }
+
+    escrow.appeal = Some(Appeal {
+        appellant: appellant.key(),
+        bond,
+        original_resolution,
+        original_arbiter,
+        appealed_at: clock.unix_timestamp,
+    });
+    escrow.pending_resolution = None;
+    escrow.ruled_by = None;
+    escrow.appeal_deadline = 0;
+    escrow.status = EscrowStatus::Disputed;
+
+    emit!(RulingAppealed {
+        escrow: escrow.key(),
+        appellant: appellant.key(),
+        resolution: original_resolution,
+        bond,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Ruling appealed by {} with a {} lamport bond",
+        appellant.key(),
+        bond
+    );
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::CONFIG_SEED,
+    errors::EscrowError,
+    events::AdminTransferCancelled,
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct CancelAdminTransfer<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let pending_admin = config
+        .pending_admin
+        .take()
+        .ok_or(EscrowError::NoPendingAdminTransfer)?;
+
+    emit!(AdminTransferCancelled {
+        admin: config.admin,
+        pending_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Admin transfer to {} cancelled", pending_admin);
+
+    Ok(())
+}
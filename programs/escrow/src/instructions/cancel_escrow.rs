@@ -1,47 +1,91 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constants::*, errors::EscrowError, events::EscrowCancelled, state::{Escrow, EscrowStatus}
+    constants::*, errors::EscrowError, events::EscrowCancelled, state::{Config, Escrow, EscrowStatus},
+    utils::{close_escrow, pay_from_escrow, TokenVault},
 };
 
 #[derive(Accounts)]
 pub struct CancelEscrow<'info> {
     #[account(
-        mut, 
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
         bump = escrow.bump,
         has_one = buyer,
-        constraint = escrow.can_cancel() @ EscrowError::InvalidState,
+        constraint = escrow.can_cancel() || (config.emergency_mode && escrow.is_active()) @ EscrowError::InvalidState,
     )]
     pub escrow: Account<'info, Escrow>,
 
+    #[account(mut)]
     pub buyer: Signer<'info>,
     /// CHECK: Seller is not involved in cancellation
     pub seller: AccountInfo<'info>,
+
+    /// Config account, in emergency mode buyers may also exit accepted escrows
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Mint of the escrowed token (token escrows only)
+    #[account(constraint = escrow.mint == Some(mint.key()) @ EscrowError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault holding the escrowed tokens (token escrows only)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyer's token account receiving the refund (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 }
 
 pub fn handler(ctx: Context<CancelEscrow>) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
     let buyer = &ctx.accounts.buyer;
     let clock = Clock::get()?;
+    let token_vault = TokenVault::from_accounts(
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    );
 
-    let amount = escrow.amount;
-
-    // Transfer funds back to buyer by directly manipulating lamports
-    **escrow.to_account_info().try_borrow_mut_lamports()? = escrow
-        .to_account_info()
-        .lamports()
-        .checked_sub(amount)
-        .ok_or(EscrowError::InsufficientFunds)?;
+    // Refund whatever has not been released to the seller yet
+    let amount = escrow.remaining_amount();
 
-    **buyer.to_account_info().try_borrow_mut_lamports()? = buyer
-        .to_account_info()
-        .lamports()
-        .checked_add(amount)
-        .ok_or(EscrowError::InsufficientFunds)?;
+    // Transfer funds back to buyer
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &buyer.to_account_info(),
+        ctx.accounts.buyer_token_account.as_ref(),
+        amount,
+    )?;
 
-    // Update escrow status
+    // Update escrow status and return the rent to the buyer
     escrow.status = EscrowStatus::Cancelled;
+    close_escrow(escrow, token_vault.as_ref(), &buyer.to_account_info())?;
 
     // Emit event
     emit!(EscrowCancelled {
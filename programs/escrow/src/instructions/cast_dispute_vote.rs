@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::DisputeVoteCast,
+    state::{Arbiter, Config, DisputeResolution, DisputeVote, Escrow, EscrowStatus, PanelVote},
+};
+
+#[derive(Accounts)]
+pub struct CastDisputeVote<'info> {
+    #[account(
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Disputed @ EscrowError::InvalidState,
+        constraint = escrow.is_panel_dispute() @ EscrowError::NotPanelDispute,
+        constraint = escrow.appeal.is_none() @ EscrowError::AppealPending,
+        constraint = escrow.is_assigned_arbiter(&arbiter.key()) @ EscrowError::ArbiterNotAssigned,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Votes of the panel, created by the first arbiter to vote
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        space = 8 + DisputeVote::LEN,
+        seeds = [DISPUTE_VOTE_SEED, escrow.key().as_ref()],
+        bump
+    )]
+    pub dispute_vote: Account<'info, DisputeVote>,
+
+    /// A panel member casting their vote
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    /// The arbiter's authorization account
+    #[account(
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = arbiter_account.can_resolve_disputes(config.min_arbiter_stake) @ EscrowError::UnauthorizedArbiter,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Used for PDA derivation
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Used for PDA derivation
+    pub seller: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CastDisputeVote>, resolution: DisputeResolution) -> Result<()> {
+    let escrow = &ctx.accounts.escrow;
+    let dispute_vote = &mut ctx.accounts.dispute_vote;
+    let arbiter = ctx.accounts.arbiter.key();
+    let clock = Clock::get()?;
+
+    require!(resolution.buyer_basis_points().is_some(), EscrowError::InvalidSplit);
+    // Votes cast by the deadline are final, only a panel nobody voted on stays open so the
+    // dispute can't get stuck
+    require!(
+        clock.unix_timestamp < escrow.voting_deadline || dispute_vote.votes.is_empty(),
+        EscrowError::VotingClosed
+    );
+    require!(!dispute_vote.has_voted(&arbiter), EscrowError::AlreadyVoted);
+    require!(
+        dispute_vote.votes.len() < escrow.panel_size as usize,
+        EscrowError::PanelFull
+    );
+
+    // First vote sets up the account
+    if dispute_vote.votes.is_empty() {
+        dispute_vote.escrow = escrow.key();
+        dispute_vote.bump = ctx.bumps.dispute_vote;
+    }
+
+    dispute_vote.votes.push(PanelVote { arbiter, resolution });
+
+    emit!(DisputeVoteCast {
+        escrow: escrow.key(),
+        arbiter,
+        resolution,
+        votes_for: dispute_vote.count(&resolution),
+        votes_cast: dispute_vote.votes.len() as u8,
+        panel_size: escrow.panel_size,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Panel vote {}/{} cast by {}",
+        dispute_vote.votes.len(),
+        escrow.panel_size,
+        arbiter
+    );
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::EscrowTimedOut,
+    state::{Config, Escrow, EscrowStatus, Reputation},
+    utils::{close_escrow, pay_from_escrow, record_trade_outcome, TokenVault, TradeOutcome},
+};
+
+#[derive(Accounts)]
+pub struct ClaimExpiredEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        has_one = buyer,
+        has_one = seller,
+        constraint = escrow.is_active() @ EscrowError::InvalidState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// The seller claiming funds the buyer never released
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    /// CHECK: Buyer receives the escrow rent
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    /// Buyer's reputation account, created with the escrow
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_reputation: Account<'info, Reputation>,
+
+    /// Seller's reputation account, created with the escrow
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_reputation: Account<'info, Reputation>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Fee collector PDA receiving platform fees
+    /// CHECK: Validated by seeds constraint
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump = config.fee_collector_bump,
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    /// Mint of the escrowed token (token escrows only)
+    #[account(constraint = escrow.mint == Some(mint.key()) @ EscrowError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault holding the escrowed tokens (token escrows only)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Seller's token account receiving the funds (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Fee collector's token account receiving platform fees (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_collector,
+        token::token_program = token_program,
+    )]
+    pub fee_collector_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ClaimExpiredEscrow>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let seller = &ctx.accounts.seller;
+    let clock = Clock::get()?;
+    let token_vault = TokenVault::from_accounts(
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    );
+
+    // Seller can only claim once the timeout passed without a dispute
+    require!(
+        escrow.has_timed_out(clock.unix_timestamp, ctx.accounts.config.escrow_timeout),
+        EscrowError::TimeoutNotReached
+    );
+
+    // Same fee as a release, at the seller's tier
+    let amount = escrow.remaining_amount();
+    let fee_amount = ctx
+        .accounts
+        .config
+        .calculate_seller_fee(amount, &ctx.accounts.seller_reputation)
+        .ok_or(EscrowError::Overflow)?;
+    let seller_amount = amount
+        .checked_sub(fee_amount)
+        .ok_or(EscrowError::InsufficientFunds)?;
+
+    // Transfer fee to fee collector
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &ctx.accounts.fee_collector,
+        ctx.accounts.fee_collector_token_account.as_ref(),
+        fee_amount,
+    )?;
+
+    // Transfer remaining funds to seller
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &seller.to_account_info(),
+        ctx.accounts.seller_token_account.as_ref(),
+        seller_amount,
+    )?;
+
+    // Update escrow status and return the rent to the buyer
+    escrow.status = EscrowStatus::Completed;
+    let volume = escrow.lamport_volume(escrow.amount);
+    close_escrow(escrow, token_vault.as_ref(), &ctx.accounts.buyer)?;
+
+    // The buyer let the dispute window pass, so the trade counts as completed for both
+    let outcome = || TradeOutcome { successful: true, volume, dispute: None };
+    record_trade_outcome(&mut ctx.accounts.buyer_reputation, outcome(), clock.unix_timestamp, "Buyer");
+    record_trade_outcome(&mut ctx.accounts.seller_reputation, outcome(), clock.unix_timestamp, "Seller");
+
+    emit!(EscrowTimedOut {
+        escrow: escrow.key(),
+        claimed_by: seller.key(),
+        amount: seller_amount,
+        fee_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Expired escrow claimed: {} to seller, {} platform fee", seller_amount, fee_amount);
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_SEED, CONFIG_SEED},
+    errors::EscrowError,
+    events::ArbiterClosed,
+    state::{Arbiter, Config},
+};
+
+#[derive(Accounts)]
+pub struct CloseArbiter<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Closed with its rent returned to the admin who paid for it
+    #[account(
+        mut,
+        close = admin,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = arbiter_account.can_close() @ EscrowError::ArbiterNotClosable,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// The arbiter being closed
+    /// CHECK: Used for PDA derivation
+    pub arbiter: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CloseArbiter>) -> Result<()> {
+    let arbiter_account = &ctx.accounts.arbiter_account;
+
+    // The arbiter can be added again later, starting with fresh statistics
+    emit!(ArbiterClosed {
+        arbiter: arbiter_account.arbiter,
+        closed_by: ctx.accounts.admin.key(),
+        disputes_resolved: arbiter_account.disputes_resolved,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Arbiter closed: {}", arbiter_account.arbiter);
+
+    Ok(())
+}
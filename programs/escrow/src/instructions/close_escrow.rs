@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::*, errors::EscrowError, events::EscrowClosed, state::Escrow};
+
+#[derive(Accounts)]
+pub struct CloseEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        close = buyer,
+        constraint = escrow.buyer == *party.key || escrow.seller == *party.key @ EscrowError::Unauthorized,
+        constraint = escrow.is_finalized() @ EscrowError::InvalidState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// The party closing the escrow (either buyer or seller)
+    pub party: Signer<'info>,
+
+    /// CHECK: Buyer funded the escrow and receives the rent
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: This is the seller account
+    pub seller: AccountInfo<'info>,
+}
+
+/// Closes an escrow that was kept open for audit after settlement. Token vaults were
+/// already closed when the escrow settled.
+pub fn handler(ctx: Context<CloseEscrow>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    emit!(EscrowClosed {
+        escrow: ctx.accounts.escrow.key(),
+        closed_by: ctx.accounts.party.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Escrow closed by {}, rent returned to buyer {}",
+        ctx.accounts.party.key(),
+        ctx.accounts.buyer.key()
+    );
+
+    Ok(())
+}
@@ -1,21 +1,30 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_interface::{
+    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 use crate::{
     constants::*,
     errors::EscrowError,
-    events::EscrowCreated,
-    state::{Escrow, EscrowStatus},
+    events::{EscrowCreated, ReputationUpdated},
+    state::{Config, Escrow, EscrowStatus, Reputation},
+    utils::is_supported_mint,
 };
 
 #[derive(Accounts)]
-#[instruction(amount: u64)]
+#[instruction(escrow_id: u64, amount: u64, milestones: Vec<u64>, arbiters: Vec<Pubkey>)]
 pub struct CreateEscrow<'info> {
     #[account(
         init,
         payer = buyer,
         space = 8 + Escrow::LEN,
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow_id.to_le_bytes().as_ref(),
+        ],
         bump
     )]
     pub escrow: Account<'info, Escrow>,
@@ -26,60 +35,226 @@ pub struct CreateEscrow<'info> {
     /// CHECK: Seller doesn't need to sign, just be a valid account
     pub seller: AccountInfo<'info>,
 
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.escrows_paused @ EscrowError::ProgramPaused,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Buyer's reputation account, created on the buyer's first escrow
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + Reputation::LEN,
+        seeds = [REPUTATION_SEED, buyer.key().as_ref()],
+        bump
+    )]
+    pub buyer_reputation: Account<'info, Reputation>,
+
+    /// Seller's reputation account, created by the buyer if the seller has none yet
+    #[account(
+        init_if_needed,
+        payer = buyer,
+        space = 8 + Reputation::LEN,
+        seeds = [REPUTATION_SEED, seller.key().as_ref()],
+        bump
+    )]
+    pub seller_reputation: Account<'info, Reputation>,
+
+    /// Mint of the escrowed token (omit for native SOL escrows)
+    #[account(
+        mint::token_program = token_program,
+        constraint = is_supported_mint(&mint.to_account_info()) @ EscrowError::UnsupportedMint,
+    )]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault token account owned by the escrow PDA (token escrows only)
+    #[account(
+        init,
+        payer = buyer,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyer's token account funding the escrow (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<CreateEscrow>, amount: u64) -> Result<()> {
+pub fn handler(
+    ctx: Context<CreateEscrow>,
+    escrow_id: u64,
+    amount: u64,
+    milestones: Vec<u64>,
+    arbiters: Vec<Pubkey>,
+    keep_open: bool,
+) -> Result<()> {
     let buyer = &ctx.accounts.buyer;
     let seller = &ctx.accounts.seller;
     let escrow = &mut ctx.accounts.escrow;
     let clock = Clock::get()?;
 
-    // Validate amount is within bounds
+    // Validate buyer and seller are different
     require!(
-        amount >= MIN_ESCROW_AMOUNT,
-        EscrowError::InsufficientFunds
+        buyer.key() != seller.key(),
+        EscrowError::InvalidParties
     );
+
+    // No milestones means a single payout of the full amount
+    let milestones = if milestones.is_empty() { vec![amount] } else { milestones };
+    let milestone_total = milestones
+        .iter()
+        .try_fold(0u64, |total, milestone| total.checked_add(*milestone))
+        .ok_or(EscrowError::Overflow)?;
     require!(
-        amount <= MAX_ESCROW_AMOUNT,
-        EscrowError::InvalidAmount
+        milestones.len() <= MAX_MILESTONES
+            && milestones.iter().all(|milestone| *milestone > 0)
+            && milestone_total == amount,
+        EscrowError::InvalidMilestones
     );
 
-    // Validate buyer and seller are different
+    // Arbiters are proposed by the buyer and agreed by the seller on acceptance
     require!(
-        buyer.key() != seller.key(),
-        EscrowError::InvalidParties
+        arbiters.len() <= MAX_ESCROW_ARBITERS
+            && arbiters.iter().all(|arbiter| *arbiter != buyer.key() && *arbiter != seller.key())
+            && arbiters
+                .iter()
+                .enumerate()
+                .all(|(i, arbiter)| !arbiters[..i].contains(arbiter)),
+        EscrowError::InvalidArbiters
     );
 
-    // Transfer funds from buyer to escrow PDA
-    let transfer_accounts = Transfer {
-        from: buyer.to_account_info(),
-        to: escrow.to_account_info(),
-    };
-    let cpi_context = CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        transfer_accounts,
+    // Escrows that would go to a panel need an explicit list of arbiters large enough to fill it
+    let config = &ctx.accounts.config;
+    require!(
+        !config.requires_panel(amount, ctx.accounts.mint.is_some())
+            || arbiters.len() >= config.panel_size as usize,
+        EscrowError::InvalidArbiters
     );
-    transfer(cpi_context, amount)?;
+
+    match &ctx.accounts.mint {
+        Some(mint) => {
+            // Token amounts are in base units, so the lamport bounds don't apply
+            require!(amount > 0, EscrowError::InvalidAmount);
+
+            let (Some(vault), Some(buyer_token_account), Some(token_program)) = (
+                &ctx.accounts.vault,
+                &ctx.accounts.buyer_token_account,
+                &ctx.accounts.token_program,
+            ) else {
+                return err!(EscrowError::MissingTokenAccounts);
+            };
+
+            // Transfer tokens from buyer to the escrow vault
+            let transfer_accounts = TransferChecked {
+                from: buyer_token_account.to_account_info(),
+                mint: mint.to_account_info(),
+                to: vault.to_account_info(),
+                authority: buyer.to_account_info(),
+            };
+            let cpi_context = CpiContext::new(
+                token_program.to_account_info(),
+                transfer_accounts,
+            );
+            token_interface::transfer_checked(cpi_context, amount, mint.decimals)?;
+
+            escrow.mint = Some(mint.key());
+        }
+        None => {
+            // Validate amount is within bounds
+            require!(
+                amount >= MIN_ESCROW_AMOUNT,
+                EscrowError::InsufficientFunds
+            );
+            require!(
+                amount <= MAX_ESCROW_AMOUNT,
+                EscrowError::InvalidAmount
+            );
+
+            // Transfer funds from buyer to escrow PDA
+            let transfer_accounts = Transfer {
+                from: buyer.to_account_info(),
+                to: escrow.to_account_info(),
+            };
+            let cpi_context = CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_accounts,
+            );
+            transfer(cpi_context, amount)?;
+
+            escrow.mint = None;
+        }
+    }
 
     // Initialize escrow account
     escrow.buyer = buyer.key();
     escrow.seller = seller.key();
+    escrow.escrow_id = escrow_id;
     escrow.amount = amount;
-    escrow.status = EscrowStatus::Active;
+    escrow.milestones = milestones;
+    escrow.milestones_released = 0;
+    escrow.released_amount = 0;
+    escrow.arbiters = arbiters;
+    escrow.dispute_reason = None;
+    escrow.disputed_by = None;
+    escrow.disputed_at = 0;
+    escrow.panel_size = 0;
+    escrow.voting_deadline = 0;
+    escrow.pending_resolution = None;
+    escrow.ruled_by = None;
+    escrow.ruling_at = 0;
+    escrow.appeal_deadline = 0;
+    escrow.appeal = None;
+    escrow.evidence_count = 0;
+    escrow.status = EscrowStatus::Initialized;
     escrow.created_at = clock.unix_timestamp;
+    escrow.accepted_at = 0;
+    escrow.keep_open = keep_open;
     escrow.bump = ctx.bumps.escrow;
 
+    // Make sure every trade can be recorded at settlement
+    if ctx.accounts.buyer_reputation.initialize_if_new(buyer.key()) {
+        emit!(ReputationUpdated::new(&ctx.accounts.buyer_reputation));
+    }
+    if ctx.accounts.seller_reputation.initialize_if_new(seller.key()) {
+        emit!(ReputationUpdated::new(&ctx.accounts.seller_reputation));
+    }
+
     // Emit event
     emit!(EscrowCreated {
         escrow: escrow.key(),
         buyer: buyer.key(),
         seller: seller.key(),
+        escrow_id,
         amount,
+        milestone_count: escrow.milestones.len() as u8,
+        arbiters: escrow.arbiters.clone(),
+        mint: escrow.mint,
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("Escrow created: {} lamports from {} to {}", amount, buyer.key(), seller.key());
+    msg!(
+        "Escrow {} created: {} units from {} to {}",
+        escrow_id,
+        amount,
+        buyer.key(),
+        seller.key()
+    );
 
     Ok(())
-}
\ No newline at end of file
+}
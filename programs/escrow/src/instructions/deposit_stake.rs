@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::{ARBITER_SEED, STAKE_VAULT_SEED},
+    errors::EscrowError,
+    events::StakeDeposited,
+    state::Arbiter,
+};
+
+#[derive(Accounts)]
+pub struct DepositStake<'info> {
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// Stake vault PDA holding the arbiter's stake
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.stake_vault_bump,
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, EscrowError::InvalidAmount);
+
+    // Transfer stake from arbiter to the stake vault
+    let transfer_accounts = Transfer {
+        from: ctx.accounts.arbiter.to_account_info(),
+        to: ctx.accounts.stake_vault.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(
+        ctx.accounts.system_program.to_account_info(),
+        transfer_accounts,
+    );
+    transfer(cpi_context, amount)?;
+
+    arbiter_account.stake = arbiter_account
+        .stake
+        .checked_add(amount)
+        .ok_or(EscrowError::Overflow)?;
+    // Topping up recommits the stake and cancels a pending withdrawal
+    arbiter_account.unstake_requested_at = 0;
+
+    emit!(StakeDeposited {
+        arbiter: arbiter_account.arbiter,
+        amount,
+        total_stake: arbiter_account.stake,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Arbiter {} staked {} lamports",
+        arbiter_account.arbiter,
+        amount
+    );
+
+    Ok(())
+}
@@ -0,0 +1,406 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::{AppealDecided, DisputeResolved},
+    state::{Arbiter, Config, DisputeResolution, DisputeVote, Escrow, EscrowStatus, Reputation},
+    utils::{
+        close_escrow, pay_from_escrow, record_trade_outcome, slash_stake, transfer_lamports,
+        update_panel_arbiters, DisputeRecord, TokenVault, TradeOutcome,
+    },
+};
+
+#[derive(Accounts)]
+pub struct ExecuteRuling<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Ruled @ EscrowError::InvalidState,
+        constraint = escrow.ruled_by == Some(arbiter.key()) @ EscrowError::NotRulingArbiter,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// CHECK: The arbiter who made the ruling, receives the arbiter fee
+    #[account(mut)]
+    pub arbiter: AccountInfo<'info>,
+
+    /// The ruling arbiter's authorization account
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// Panel votes (panel disputes only), closed once the ruling is executed
+    #[account(
+        mut,
+        seeds = [DISPUTE_VOTE_SEED, escrow.key().as_ref()],
+        bump = dispute_vote.bump,
+    )]
+    pub dispute_vote: Option<Account<'info, DisputeVote>>,
+
+    /// Authorization account of the arbiter whose ruling was appealed (appeals only),
+    /// slashed when the appeal overturned it. It is charged a lost appeal too, unless a panel
+    /// made the ruling and it didn't vote for it.
+    #[account(mut)]
+    pub original_arbiter_account: Option<Account<'info, Arbiter>>,
+
+    /// Stake vault of the arbiter whose ruling was appealed (appeals only)
+    /// CHECK: Validated against the arbiter's stake vault bump when slashing
+    #[account(mut)]
+    pub original_stake_vault: Option<AccountInfo<'info>>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Fee collector PDA receiving platform fees
+    /// CHECK: Validated by seeds constraint
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump = config.fee_collector_bump,
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    /// CHECK: Buyer account for refund
+    #[account(mut)]
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Seller account for payment
+    #[account(mut)]
+    pub seller: AccountInfo<'info>,
+
+    /// Buyer's reputation account, created with the escrow
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, buyer.key().as_ref()],
+        bump,
+    )]
+    pub buyer_reputation: Account<'info, Reputation>,
+
+    /// Seller's reputation account, created with the escrow
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, seller.key().as_ref()],
+        bump,
+    )]
+    pub seller_reputation: Account<'info, Reputation>,
+
+    /// Mint of the escrowed token (token escrows only)
+    #[account(constraint = escrow.mint == Some(mint.key()) @ EscrowError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault holding the escrowed tokens (token escrows only)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Buyer's token account (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Seller's token account (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Arbiter's token account receiving the arbiter fee (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = arbiter,
+        token::token_program = token_program,
+    )]
+    pub arbiter_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Fee collector's token account receiving platform fees (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_collector,
+        token::token_program = token_program,
+    )]
+    pub fee_collector_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Pays out a final ruling. Anyone can execute it once the appeal window has closed or an
+/// appeal has been decided. For panel disputes the panel members' wallets are passed as
+/// remaining accounts, in the order they voted. When an appeal overturns a panel's ruling
+/// their arbiter accounts follow, in the same order.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteRuling<'info>>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let buyer = &ctx.accounts.buyer;
+    let seller = &ctx.accounts.seller;
+    let clock = Clock::get()?;
+    let token_vault = TokenVault::from_accounts(
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    );
+
+    require!(
+        escrow.can_execute_ruling(clock.unix_timestamp),
+        EscrowError::AppealWindowOpen
+    );
+    let resolution = escrow.pending_resolution.ok_or(EscrowError::InvalidState)?;
+
+    // The dispute covers only milestones that haven't been released
+    let amount = escrow.remaining_amount();
+
+    // Arbiter and platform are compensated out of the disputed amount before it is split
+    let config = &ctx.accounts.config;
+    let arbiter_fee = config
+        .calculate_arbiter_fee(amount, escrow.is_token_escrow())
+        .ok_or(EscrowError::Overflow)?;
+    let platform_fee = config
+        .calculate_dispute_fee(amount, &resolution)
+        .ok_or(EscrowError::Overflow)?
+        .min(amount.saturating_sub(arbiter_fee));
+    let distributable = amount
+        .checked_sub(arbiter_fee)
+        .and_then(|remaining| remaining.checked_sub(platform_fee))
+        .ok_or(EscrowError::InsufficientFunds)?;
+
+    // Shares always add up to the distributable amount
+    let (buyer_amount, seller_amount) = resolution
+        .split_amount(distributable)
+        .ok_or(EscrowError::InvalidSplit)?;
+
+    // Panel disputes must close their vote account with the escrow, so it can't outlive it
+    let dispute_vote = if escrow.is_panel_dispute() {
+        Some(
+            ctx.accounts
+                .dispute_vote
+                .as_ref()
+                .ok_or(EscrowError::InvalidPanelAccounts)?,
+        )
+    } else {
+        None
+    };
+    let (panel, panel_arbiters) = ctx.remaining_accounts.split_at(
+        dispute_vote
+            .map_or(0, |dispute_vote| dispute_vote.votes.len())
+            .min(ctx.remaining_accounts.len()),
+    );
+    match dispute_vote {
+        // The panel is paid when its own decision is carried out
+        Some(dispute_vote) if escrow.appeal.is_none() => {
+            pay_panel(escrow, dispute_vote, panel, arbiter_fee)?;
+        }
+        // The ruling arbiter otherwise, an appealed panel only gets its rent back
+        _ => {
+            pay_from_escrow(
+                escrow,
+                token_vault.as_ref(),
+                &ctx.accounts.arbiter,
+                ctx.accounts.arbiter_token_account.as_ref(),
+                arbiter_fee,
+            )?;
+            if let Some(dispute_vote) = dispute_vote {
+                pay_panel(escrow, dispute_vote, panel, 0)?;
+            }
+        }
+    }
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &ctx.accounts.fee_collector,
+        ctx.accounts.fee_collector_token_account.as_ref(),
+        platform_fee,
+    )?;
+
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        buyer,
+        ctx.accounts.buyer_token_account.as_ref(),
+        buyer_amount,
+    )?;
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        seller,
+        ctx.accounts.seller_token_account.as_ref(),
+        seller_amount,
+    )?;
+
+    // A successful appeal gets its bond back, a frivolous one forfeits it to the other party
+    if let Some(appeal) = escrow.appeal {
+        let overturned = appeal.is_overturned_by(&resolution, &escrow.buyer);
+        let bond_to_buyer = overturned == (appeal.appellant == escrow.buyer);
+        let bond_recipient = if bond_to_buyer { buyer } else { seller };
+        transfer_lamports(&escrow.to_account_info(), bond_recipient, appeal.bond)?;
+
+        // The appealed ruling is settled too
+        let (Some(original_arbiter_account), Some(original_stake_vault)) = (
+            ctx.accounts.original_arbiter_account.as_mut(),
+            ctx.accounts.original_stake_vault.as_ref(),
+        ) else {
+            return err!(EscrowError::MissingSlashAccounts);
+        };
+        require_keys_eq!(
+            original_arbiter_account.arbiter,
+            appeal.original_arbiter,
+            EscrowError::MissingSlashAccounts
+        );
+        original_arbiter_account.complete_ruling();
+
+        // Overturning a ruling proves the original arbiter wrong, the appellant is compensated
+        if overturned {
+            match dispute_vote {
+                // Every panel member who voted for the overturned ruling lost the appeal
+                Some(dispute_vote) => update_panel_arbiters(
+                    &dispute_vote.votes,
+                    panel_arbiters,
+                    Some(&mut *original_arbiter_account),
+                    |vote, arbiter_account| {
+                        if vote.resolution == appeal.original_resolution {
+                            arbiter_account.record_appeal_lost();
+                        }
+                    },
+                )?,
+                None => original_arbiter_account.record_appeal_lost(),
+            }
+            slash_stake(
+                original_arbiter_account,
+                original_stake_vault,
+                bond_recipient,
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.accounts.config.overturn_slash,
+                escrow.key(),
+                "Ruling overturned on appeal".to_string(),
+            )?;
+        }
+
+        emit!(AppealDecided {
+            escrow: escrow.key(),
+            appellant: appeal.appellant,
+            original_resolution: appeal.original_resolution,
+            resolution,
+            overturned,
+            bond: appeal.bond,
+            bond_recipient: bond_recipient.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!(
+        "Ruling executed: {} to buyer, {} to seller, {} arbiter fee, {} platform fee",
+        buyer_amount,
+        seller_amount,
+        arbiter_fee,
+        platform_fee
+    );
+
+    ctx.accounts.arbiter_account.complete_ruling();
+
+    // Update escrow status to Completed and return the rent to the buyer
+    escrow.status = EscrowStatus::Completed;
+    close_escrow(escrow, token_vault.as_ref(), buyer)?;
+
+    // Update reputations based on resolution: the favored party succeeds,
+    // any split means both parties share responsibility (both get failed trade)
+    let (buyer_successful, seller_successful) = match resolution {
+        DisputeResolution::FavorBuyer => (true, false),
+        DisputeResolution::FavorSeller => (false, true),
+        DisputeResolution::Split | DisputeResolution::PartialSplit { .. } => (false, false),
+    };
+    // A party loses the dispute when the ruling favors the other side
+    let volume = escrow.lamport_volume(escrow.amount);
+    let disputed_volume = escrow.lamport_volume(amount);
+    let buyer_outcome = TradeOutcome {
+        successful: buyer_successful,
+        volume,
+        dispute: Some(DisputeRecord {
+            volume: disputed_volume,
+            raised: escrow.was_disputed_by(&escrow.buyer),
+            lost: seller_successful,
+        }),
+    };
+    let seller_outcome = TradeOutcome {
+        successful: seller_successful,
+        volume,
+        dispute: Some(DisputeRecord {
+            volume: disputed_volume,
+            raised: escrow.was_disputed_by(&escrow.seller),
+            lost: buyer_successful,
+        }),
+    };
+    record_trade_outcome(&mut ctx.accounts.buyer_reputation, buyer_outcome, clock.unix_timestamp, "Buyer");
+    record_trade_outcome(&mut ctx.accounts.seller_reputation, seller_outcome, clock.unix_timestamp, "Seller");
+
+    // Emit event
+    emit!(DisputeResolved {
+        escrow: escrow.key(),
+        arbiter: ctx.accounts.arbiter.key(),
+        resolution,
+        buyer_amount,
+        seller_amount,
+        arbiter_fee,
+        platform_fee,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Shares `arbiter_fee` between the panel members and returns the vote account's
+/// rent to the arbiter who opened it. Panels only decide SOL escrows.
+fn pay_panel<'info>(
+    escrow: &Account<'info, Escrow>,
+    dispute_vote: &Account<'info, DisputeVote>,
+    panel: &[AccountInfo<'info>],
+    arbiter_fee: u64,
+) -> Result<()> {
+    require!(
+        panel.len() == dispute_vote.votes.len()
+            && panel
+                .iter()
+                .zip(&dispute_vote.votes)
+                .all(|(account, vote)| account.key() == vote.arbiter),
+        EscrowError::InvalidPanelAccounts
+    );
+    let first_voter = panel.first().ok_or(EscrowError::InvalidPanelAccounts)?;
+
+    // The first voter also receives whatever doesn't divide evenly
+    let share = arbiter_fee / panel.len() as u64;
+    let remainder = arbiter_fee % panel.len() as u64;
+    for (i, account) in panel.iter().enumerate() {
+        let amount = if i == 0 { share + remainder } else { share };
+        transfer_lamports(&escrow.to_account_info(), account, amount)?;
+    }
+
+    dispute_vote.close(first_voter.clone())
+}
@@ -35,13 +35,14 @@ pub fn handler(ctx: Context<InitializeReputation>) -> Result<()> {
     reputation.user = user.key();
     reputation.successful_trades = 0;
     reputation.failed_trades = 0;
+    reputation.total_volume = 0;
+    reputation.disputed_volume = 0;
+    reputation.disputes_raised = 0;
+    reputation.disputes_lost = 0;
+    reputation.last_activity_at = 0;
 
     // Emit reputation initialized event
-    emit!(ReputationUpdated {
-        user: user.key(),
-        successful_trades: 0,
-        failed_trades: 0,
-    });
+    emit!(ReputationUpdated::new(reputation));
 
     Ok(())
 }
@@ -30,22 +30,37 @@ pub struct InitializeConfig<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<InitializeConfig>, fee_basis_points: u16) -> Result<()> {
+pub fn handler(
+    ctx: Context<InitializeConfig>,
+    fee_basis_points: u16,
+    arbiter_fee_basis_points: u16,
+    min_arbiter_fee: u64,
+) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let admin = &ctx.accounts.admin;
     let fee_collector = &ctx.accounts.fee_collector;
 
     // Validate fee is reasonable (max 10% = 1000 basis points)
     require!(fee_basis_points <= 1000, ConfigError::FeeTooHigh);
+    require!(arbiter_fee_basis_points <= 1000, ConfigError::ArbiterFeeTooHigh);
 
     // Initialize config
-    config.admin = admin.key();
-    config.fee_basis_points = fee_basis_points;
-    config.bump = ctx.bumps.config;
-    config.fee_collector_bump = ctx.bumps.fee_collector;
+    config.set_inner(Config::new(
+        admin.key(),
+        fee_basis_points,
+        arbiter_fee_basis_points,
+        min_arbiter_fee,
+        ctx.bumps.config,
+        ctx.bumps.fee_collector,
+    ));
 
     msg!("Config initialized with admin: {}", config.admin);
     msg!("Fee: {} basis points ({}%)", fee_basis_points, fee_basis_points as f64 / 100.0);
+    msg!(
+        "Arbiter fee: {} basis points, minimum {} lamports",
+        arbiter_fee_basis_points,
+        min_arbiter_fee
+    );
     msg!("Fee collector PDA: {}", fee_collector.key());
 
     Ok(())
@@ -55,8 +70,14 @@ pub fn handler(ctx: Context<InitializeConfig>, fee_basis_points: u16) -> Result<
 pub enum ConfigError {
     #[msg("Fee cannot exceed 10% (1000 basis points)")]
     FeeTooHigh,
+    #[msg("Arbiter fee cannot exceed 10% (1000 basis points)")]
+    ArbiterFeeTooHigh,
     #[msg("Unauthorized action")]
     Unauthorized,
     #[msg("Insufficient funds")]
     InsufficientFunds,
+    #[msg("Panel size must be odd, at least MIN_PANEL_SIZE and fit the assigned arbiters")]
+    InvalidPanelSize,
+    #[msg("Escrow timeout must be longer than the dispute window")]
+    InvalidEscrowTimeout,
 }
@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{
+    constants::{ARBITER_SEED, STAKE_VAULT_SEED},
+    errors::EscrowError,
+    state::Arbiter,
+};
+
+/// Arbiter layout from before staking and ruling statistics
+#[derive(AnchorDeserialize)]
+struct LegacyArbiter {
+    arbiter: Pubkey,
+    added_by: Pubkey,
+    added_at: i64,
+    is_active: bool,
+    bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateArbiter<'info> {
+    /// Arbiter account created with the original layout
+    /// CHECK: The old layout can't be deserialized as `Arbiter`, validated by seeds, owner
+    /// and discriminator in the handler
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub arbiter_account: UncheckedAccount<'info>,
+
+    /// Stake vault PDA, its bump wasn't stored by the old layout
+    /// CHECK: PDA will be validated by seeds constraint
+    #[account(
+        seeds = [STAKE_VAULT_SEED, arbiter.key().as_ref()],
+        bump
+    )]
+    pub stake_vault: AccountInfo<'info>,
+
+    /// The arbiter whose account is being migrated
+    /// CHECK: This can be any valid account
+    pub arbiter: AccountInfo<'info>,
+
+    /// Pays the rent for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resizes an arbiter account to the current layout. The arbiter keeps its status and
+/// history, and starts without stake or ruling statistics.
+pub fn handler(ctx: Context<MigrateArbiter>) -> Result<()> {
+    let arbiter_account = ctx.accounts.arbiter_account.to_account_info();
+    let old_len = arbiter_account.data_len();
+    let new_len = 8 + Arbiter::LEN;
+
+    require!(
+        arbiter_account
+            .try_borrow_data()?
+            .starts_with(Arbiter::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(old_len < new_len, EscrowError::AlreadyMigrated);
+
+    let legacy = LegacyArbiter::deserialize(&mut &arbiter_account.try_borrow_data()?[8..])?;
+
+    // Top up to the rent-exempt minimum of the new size
+    let required = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(arbiter_account.lamports());
+    if required > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: arbiter_account.clone(),
+        };
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+        );
+        transfer(cpi_context, required)?;
+    }
+
+    let mut migrated = Arbiter::new(
+        legacy.arbiter,
+        legacy.added_by,
+        legacy.added_at,
+        legacy.bump,
+        ctx.bumps.stake_vault,
+    );
+    migrated.is_active = legacy.is_active;
+
+    arbiter_account.resize(new_len)?;
+    let mut data = arbiter_account.try_borrow_mut_data()?;
+    data.fill(0);
+    migrated.try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "Arbiter account for {} migrated from {} to {} bytes",
+        legacy.arbiter,
+        old_len,
+        new_len
+    );
+
+    Ok(())
+}
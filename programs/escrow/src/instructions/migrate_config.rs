@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{
+    constants::CONFIG_SEED, errors::EscrowError, instructions::initialize_config::ConfigError,
+    state::Config,
+};
+
+/// Config layout from before the arbiter, dispute, pause, panel, appeal and stake settings
+#[derive(AnchorDeserialize)]
+struct LegacyConfig {
+    admin: Pubkey,
+    fee_basis_points: u16,
+    bump: u8,
+    fee_collector_bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    /// Config account created with the original layout
+    /// CHECK: The old layout can't be deserialized as `Config`, validated by seeds, owner
+    /// and discriminator in the handler
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump,
+        owner = crate::ID,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The admin recorded in the old config, pays the rent for the extra space
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resizes the config to the current layout. The admin, platform fee and bumps are kept,
+/// the arbiter fee is set here like in `initialize_config` and everything else gets its default.
+pub fn handler(
+    ctx: Context<MigrateConfig>,
+    arbiter_fee_basis_points: u16,
+    min_arbiter_fee: u64,
+) -> Result<()> {
+    let config = ctx.accounts.config.to_account_info();
+    let admin = &ctx.accounts.admin;
+    let old_len = config.data_len();
+    let new_len = 8 + Config::LEN;
+
+    require!(
+        config.try_borrow_data()?.starts_with(Config::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(old_len < new_len, EscrowError::AlreadyMigrated);
+
+    let legacy = LegacyConfig::deserialize(&mut &config.try_borrow_data()?[8..])?;
+    require_keys_eq!(legacy.admin, admin.key(), EscrowError::Unauthorized);
+    require!(arbiter_fee_basis_points <= 1000, ConfigError::ArbiterFeeTooHigh);
+
+    // Top up to the rent-exempt minimum of the new size
+    let required = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(config.lamports());
+    if required > 0 {
+        let transfer_accounts = Transfer {
+            from: admin.to_account_info(),
+            to: config.clone(),
+        };
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+        );
+        transfer(cpi_context, required)?;
+    }
+
+    config.resize(new_len)?;
+    let mut data = config.try_borrow_mut_data()?;
+    data.fill(0);
+    Config::new(
+        legacy.admin,
+        legacy.fee_basis_points,
+        arbiter_fee_basis_points,
+        min_arbiter_fee,
+        legacy.bump,
+        legacy.fee_collector_bump,
+    )
+    .try_serialize(&mut &mut data[..])?;
+
+    msg!(
+        "Config migrated from {} to {} bytes for admin {}",
+        old_len,
+        new_len,
+        legacy.admin
+    );
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+
+use crate::{constants::REPUTATION_SEED, errors::EscrowError, state::Reputation};
+
+#[derive(Accounts)]
+pub struct MigrateReputation<'info> {
+    /// Reputation account created before the volume fields were added
+    /// CHECK: The old layout can't be deserialized as `Reputation`, validated by seeds, owner
+    /// and discriminator in the handler
+    #[account(
+        mut,
+        seeds = [REPUTATION_SEED, user.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    pub reputation: UncheckedAccount<'info>,
+
+    /// The user whose reputation is being migrated
+    /// CHECK: This can be any valid account
+    pub user: AccountInfo<'info>,
+
+    /// Pays the rent for the extra space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Resizes a reputation account to the current layout. Existing fields keep their values,
+/// the new ones start at zero.
+pub fn handler(ctx: Context<MigrateReputation>) -> Result<()> {
+    let reputation = ctx.accounts.reputation.to_account_info();
+    let old_len = reputation.data_len();
+    let new_len = 8 + Reputation::LEN;
+
+    require!(
+        reputation
+            .try_borrow_data()?
+            .starts_with(Reputation::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(old_len < new_len, EscrowError::AlreadyMigrated);
+
+    // Top up to the rent-exempt minimum of the new size
+    let required = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(reputation.lamports());
+    if required > 0 {
+        let transfer_accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: reputation.clone(),
+        };
+        let cpi_context = CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_accounts,
+        );
+        transfer(cpi_context, required)?;
+    }
+
+    reputation.resize(new_len)?;
+    reputation.try_borrow_mut_data()?[old_len..].fill(0);
+
+    msg!(
+        "Reputation account for {} migrated from {} to {} bytes",
+        ctx.accounts.user.key(),
+        old_len,
+        new_len
+    );
+
+    Ok(())
+}
@@ -1,25 +1,75 @@
 pub mod create_escrow;
+pub mod accept_escrow;
 pub mod release_funds;
 pub mod cancel_escrow;
+pub mod close_escrow;
 pub mod refund_buyer;
 pub mod raise_dispute;
+pub mod submit_evidence;
+pub mod cast_dispute_vote;
 pub mod resolve_dispute;
+pub mod appeal_ruling;
+pub mod execute_ruling;
 pub mod initialize;
 pub mod update_reputation;
+pub mod migrate_reputation;
 pub mod initialize_config;
+pub mod update_config;
+pub mod migrate_config;
+pub mod set_fee_tiers;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod cancel_admin_transfer;
+pub mod set_guardian;
+pub mod set_pause;
 pub mod add_arbiter;
 pub mod remove_arbiter;
+pub mod reactivate_arbiter;
+pub mod migrate_arbiter;
+pub mod close_arbiter;
+pub mod deposit_stake;
+pub mod request_unstake;
+pub mod withdraw_stake;
+pub mod slash_arbiter;
 pub mod withdraw_fees;
+pub mod add_reputation_authority;
+pub mod remove_reputation_authority;
+pub mod claim_expired_escrow;
 
 pub use create_escrow::*;
+pub use accept_escrow::*;
 pub use release_funds::*;
 pub use cancel_escrow::*;
+pub use close_escrow::*;
 pub use refund_buyer::*;
 pub use raise_dispute::*;
+pub use submit_evidence::*;
+pub use cast_dispute_vote::*;
 pub use resolve_dispute::*;
+pub use appeal_ruling::*;
+pub use execute_ruling::*;
 pub use initialize::*;
 pub use update_reputation::*;
+pub use migrate_reputation::*;
 pub use initialize_config::*;
+pub use update_config::*;
+pub use migrate_config::*;
+pub use set_fee_tiers::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use cancel_admin_transfer::*;
+pub use set_guardian::*;
+pub use set_pause::*;
 pub use add_arbiter::*;
 pub use remove_arbiter::*;
+pub use reactivate_arbiter::*;
+pub use migrate_arbiter::*;
+pub use close_arbiter::*;
+pub use deposit_stake::*;
+pub use request_unstake::*;
+pub use withdraw_stake::*;
+pub use slash_arbiter::*;
 pub use withdraw_fees::*;
+pub use add_reputation_authority::*;
+pub use remove_reputation_authority::*;
+pub use claim_expired_escrow::*;
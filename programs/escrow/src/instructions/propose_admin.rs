@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::CONFIG_SEED,
+    errors::EscrowError,
+    events::AdminTransferProposed,
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(
+        new_admin != config.admin && new_admin != Pubkey::default(),
+        EscrowError::InvalidAdmin
+    );
+
+    // Replaces any transfer that is still pending
+    config.pending_admin = Some(new_admin);
+
+    emit!(AdminTransferProposed {
+        admin: config.admin,
+        pending_admin: new_admin,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Admin transfer proposed to: {}", new_admin);
+
+    Ok(())
+}
@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
 use crate::{
-    constants::*, errors::EscrowError, events::DisputeRaised, state::{Escrow, EscrowStatus}
+    constants::*, errors::EscrowError, events::DisputeRaised, state::{Config, DisputeReason, Escrow, EscrowStatus}
 };
 
 #[derive(Accounts)]
 pub struct RaiseDispute<'info> {
     #[account(
         mut,
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
         bump = escrow.bump,
         constraint = escrow.buyer == *party.key || escrow.seller == *party.key @ EscrowError::Unauthorized,
         constraint = matches!(escrow.status, EscrowStatus::Active) @ EscrowError::InvalidState,
@@ -22,19 +27,47 @@ pub struct RaiseDispute<'info> {
 
     /// CHECK: This is the seller account
     pub seller: AccountInfo<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
 }
 
-pub fn handler(ctx: Context<RaiseDispute>) -> Result<()> {
+pub fn handler(ctx: Context<RaiseDispute>, reason: DisputeReason) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
     let clock = Clock::get()?;
 
-    // Update escrow status to Disputed
+    // Disputes can only be raised within the dispute window
+    require!(
+        escrow.is_dispute_window_open(clock.unix_timestamp, ctx.accounts.config.dispute_window),
+        EscrowError::DisputeWindowClosed
+    );
+
+    // Update escrow status to Disputed and record why
     escrow.status = EscrowStatus::Disputed;
+    escrow.dispute_reason = Some(reason);
+    escrow.disputed_by = Some(ctx.accounts.party.key());
+    escrow.disputed_at = clock.unix_timestamp;
+
+    // High-value disputes are decided by a panel of arbiters
+    escrow.panel_size = ctx.accounts.config.panel_size_for(escrow);
+    escrow.voting_deadline = if escrow.is_panel_dispute() {
+        clock
+            .unix_timestamp
+            .checked_add(ctx.accounts.config.voting_period as i64)
+            .ok_or(EscrowError::Overflow)?
+    } else {
+        0
+    };
 
     // Emit DisputeRaised event
     emit!(DisputeRaised {
         escrow: escrow.key(),
         raised_by: ctx.accounts.party.key(),
+        reason,
         timestamp: clock.unix_timestamp,
     });
 
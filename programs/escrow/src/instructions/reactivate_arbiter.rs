@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_SEED, CONFIG_SEED},
+    errors::EscrowError,
+    events::ArbiterReactivated,
+    state::{Arbiter, Config},
+};
+
+#[derive(Accounts)]
+pub struct ReactivateArbiter<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = !arbiter_account.is_active @ EscrowError::ArbiterActive,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// The arbiter being reactivated
+    /// CHECK: Used for PDA derivation
+    pub arbiter: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReactivateArbiter>) -> Result<()> {
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+
+    // Statistics and stake carry over from before the removal
+    arbiter_account.is_active = true;
+
+    emit!(ArbiterReactivated {
+        arbiter: arbiter_account.arbiter,
+        reactivated_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Arbiter reactivated: {}", arbiter_account.arbiter);
+    msg!("Reactivated by admin: {}", ctx.accounts.admin.key());
+
+    Ok(())
+}
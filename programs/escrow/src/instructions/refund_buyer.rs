@@ -1,16 +1,23 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
-    constants::{ESCROW_SEED, REPUTATION_SEED},
+    constants::{CONFIG_SEED, ESCROW_SEED, FEE_COLLECTOR_SEED, REPUTATION_SEED, VAULT_SEED},
     errors::EscrowError,
-    events::{RefundIssued, ReputationUpdated},
-    state::{Escrow, EscrowStatus, Reputation},
+    events::RefundIssued,
+    state::{Config, DisputeResolution, Escrow, EscrowStatus, Reputation},
+    utils::{close_escrow, pay_from_escrow, record_trade_outcome, TokenVault, TradeOutcome},
 };
 
 #[derive(Accounts)]
 pub struct RefundBuyer<'info> {
     #[account(
         mut,
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
         bump = escrow.bump,
         has_one = buyer,
         has_one = seller,
@@ -20,98 +27,145 @@ pub struct RefundBuyer<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
-    /// CHECK: This is the seller account
+    /// Seller agreeing to give up the unreleased funds, so a refund is never one-sided
     #[account(mut)]
-    pub seller: SystemAccount<'info>,
+    pub seller: Signer<'info>,
 
-    /// Buyer's reputation account (optional)
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Fee collector PDA receiving platform fees
+    /// CHECK: Validated by seeds constraint
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump = config.fee_collector_bump,
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    /// Buyer's reputation account, created with the escrow
     #[account(
         mut,
         seeds = [REPUTATION_SEED, buyer.key().as_ref()],
         bump,
     )]
-    pub buyer_reputation: Option<Account<'info, Reputation>>,
+    pub buyer_reputation: Account<'info, Reputation>,
 
-    /// Seller's reputation account (optional)
+    /// Seller's reputation account, created with the escrow
     #[account(
         mut,
         seeds = [REPUTATION_SEED, seller.key().as_ref()],
         bump,
     )]
-    pub seller_reputation: Option<Account<'info, Reputation>>,
+    pub seller_reputation: Account<'info, Reputation>,
 
-    pub system_program: Program<'info, System>,
-}
+    /// Mint of the escrowed token (token escrows only)
+    #[account(constraint = escrow.mint == Some(mint.key()) @ EscrowError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
 
-pub fn handler(ctx: Context<RefundBuyer>) -> Result<()> {
-    let escrow = &mut ctx.accounts.escrow;
-    
-    // Check that escrow is in Disputed state
-    require!(
-        matches!(escrow.status, EscrowStatus::Disputed),
-        EscrowError::InvalidState
-    );
+    /// Vault holding the escrowed tokens (token escrows only)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    // Get the amount to refund
-    let refund_amount = escrow.amount;
+    /// Buyer's token account receiving the refund (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = buyer,
+        token::token_program = token_program,
+    )]
+    pub buyer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    // Transfer funds from escrow PDA back to buyer using direct lamport manipulation
-    let escrow_account_info = escrow.to_account_info();
-    let buyer_account_info = ctx.accounts.buyer.to_account_info();
+    /// Fee collector's token account receiving platform fees (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_collector,
+        token::token_program = token_program,
+    )]
+    pub fee_collector_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 
-    // Get the rent-exempt reserve for the escrow account
-    let rent = Rent::get()?;
-    let escrow_rent_reserve = rent.minimum_balance(escrow_account_info.data_len());
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 
-    // Calculate available lamports (total - rent reserve)
-    let escrow_lamports = escrow_account_info.lamports();
-    require!(
-        escrow_lamports > escrow_rent_reserve,
-        EscrowError::InsufficientFunds
-    );
+    pub system_program: Program<'info, System>,
+}
 
-    // Transfer lamports back to buyer
-    **escrow_account_info.try_borrow_mut_lamports()? = escrow_rent_reserve;
-    **buyer_account_info.try_borrow_mut_lamports()? = buyer_account_info
-        .lamports()
-        .checked_add(escrow_lamports - escrow_rent_reserve)
+pub fn handler(ctx: Context<RefundBuyer>) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    
+    // Only active escrows can be refunded, by both parties. Once disputed the funds are
+    // settled by `resolve_dispute` and `execute_ruling`
+    require!(escrow.is_active(), EscrowError::InvalidState);
+
+    // Only milestones that haven't been released are refunded, less the
+    // platform fee for a buyer-favored outcome
+    let amount = escrow.remaining_amount();
+    let fee_amount = ctx
+        .accounts
+        .config
+        .calculate_dispute_fee(amount, &DisputeResolution::FavorBuyer)
         .ok_or(EscrowError::Overflow)?;
+    let refund_amount = amount
+        .checked_sub(fee_amount)
+        .ok_or(EscrowError::InsufficientFunds)?;
+
+    let token_vault = TokenVault::from_accounts(
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    );
 
-    // Update escrow status to Cancelled
+    // Transfer fee to fee collector
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &ctx.accounts.fee_collector,
+        ctx.accounts.fee_collector_token_account.as_ref(),
+        fee_amount,
+    )?;
+
+    // Transfer funds from escrow back to buyer
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &ctx.accounts.buyer.to_account_info(),
+        ctx.accounts.buyer_token_account.as_ref(),
+        refund_amount,
+    )?;
+
+    // Update escrow status to Cancelled and return the rent to the buyer
     escrow.status = EscrowStatus::Cancelled;
+    close_escrow(escrow, token_vault.as_ref(), &ctx.accounts.buyer.to_account_info())?;
 
-    // Update reputation for buyer if account exists (failed trade)
-    if let Some(buyer_reputation) = &mut ctx.accounts.buyer_reputation {
-        buyer_reputation.increment_failed();
-        emit!(ReputationUpdated {
-            user: buyer_reputation.user,
-            successful_trades: buyer_reputation.successful_trades,
-            failed_trades: buyer_reputation.failed_trades,
-        });
-        msg!("Buyer reputation updated: {} failed trades", buyer_reputation.failed_trades);
-    }
-
-    // Update reputation for seller if account exists (failed trade)
-    if let Some(seller_reputation) = &mut ctx.accounts.seller_reputation {
-        seller_reputation.increment_failed();
-        emit!(ReputationUpdated {
-            user: seller_reputation.user,
-            successful_trades: seller_reputation.successful_trades,
-            failed_trades: seller_reputation.failed_trades,
-        });
-        msg!("Seller reputation updated: {} failed trades", seller_reputation.failed_trades);
-    }
+    // Both parties record a failed trade
+    let clock = Clock::get()?;
+    let volume = escrow.lamport_volume(escrow.amount);
+    let outcome = || TradeOutcome { successful: false, volume, dispute: None };
+    record_trade_outcome(&mut ctx.accounts.buyer_reputation, outcome(), clock.unix_timestamp, "Buyer");
+    record_trade_outcome(&mut ctx.accounts.seller_reputation, outcome(), clock.unix_timestamp, "Seller");
 
     // Emit RefundIssued event
     emit!(RefundIssued {
         escrow: escrow.key(),
         buyer: ctx.accounts.buyer.key(),
         amount: refund_amount,
-        reason: "Disputed escrow refund".to_string(),
-        timestamp: Clock::get()?.unix_timestamp,
+        fee_amount,
+        reason: "Buyer refund".to_string(),
+        timestamp: clock.unix_timestamp,
     });
 
-    msg!("Buyer refunded {} lamports from disputed escrow", refund_amount);
+    msg!("Buyer refunded {} from active escrow, {} platform fee", refund_amount, fee_amount);
 
     Ok(())
 }
@@ -1,17 +1,24 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     constants::*,
     errors::EscrowError,
-    events::{FundsReleased, ReputationUpdated},
+    events::FundsReleased,
     state::{Config, Escrow, EscrowStatus, Reputation},
+    utils::{close_escrow, pay_from_escrow, record_trade_outcome, TokenVault, TradeOutcome},
 };
 
 #[derive(Accounts)]
 pub struct ReleaseFunds<'info> {
     #[account(
         mut,
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
         bump = escrow.bump,
         has_one = buyer,
         has_one = seller,
@@ -26,30 +33,73 @@ pub struct ReleaseFunds<'info> {
     #[account(mut)]
     pub seller: AccountInfo<'info>,
 
-    /// Buyer's reputation account (optional)
+    /// Buyer's reputation account, created with the escrow
     #[account(
         mut,
         seeds = [REPUTATION_SEED, buyer.key().as_ref()],
         bump,
     )]
-    pub buyer_reputation: Option<Account<'info, Reputation>>,
+    pub buyer_reputation: Account<'info, Reputation>,
 
-    /// Seller's reputation account (optional)
+    /// Seller's reputation account, created with the escrow
     #[account(
         mut,
         seeds = [REPUTATION_SEED, seller.key().as_ref()],
         bump,
     )]
-    pub seller_reputation: Option<Account<'info, Reputation>>,
+    pub seller_reputation: Account<'info, Reputation>,
 
-    /// Config account for fee settings (optional - no constraints to allow truly optional)
-    #[account(mut)]
-    pub config: Option<Account<'info, Config>>,
+    /// Config account for fee settings and pause flags
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
 
-    /// Fee collector account (optional, receives platform fees)
-    /// CHECK: Fee collector receives platform fees, validated manually in handler
-    #[account(mut)]
-    pub fee_collector: Option<AccountInfo<'info>>,
+    /// Fee collector PDA receiving platform fees
+    /// CHECK: Validated by seeds constraint
+    #[account(
+        mut,
+        seeds = [FEE_COLLECTOR_SEED],
+        bump = config.fee_collector_bump,
+    )]
+    pub fee_collector: AccountInfo<'info>,
+
+    /// Mint of the escrowed token (token escrows only)
+    #[account(constraint = escrow.mint == Some(mint.key()) @ EscrowError::InvalidMint)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Vault holding the escrowed tokens (token escrows only)
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, escrow.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Seller's token account receiving the funds (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = seller,
+        token::token_program = token_program,
+    )]
+    pub seller_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Fee collector's token account receiving platform fees (token escrows only)
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_collector,
+        token::token_program = token_program,
+    )]
+    pub fee_collector_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -58,103 +108,90 @@ pub fn handler(ctx: Context<ReleaseFunds>) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
     let seller = &ctx.accounts.seller;
     let clock = Clock::get()?;
-
-    let amount = escrow.amount;
-    let mut fee_amount = 0u64;
-    let mut seller_amount = amount;
-
-    // Calculate and deduct platform fee if config is provided
-    if let Some(config) = &ctx.accounts.config {
-        if let Some(fee_collector) = &ctx.accounts.fee_collector {
-            // Validate config PDA
-            let (expected_config_key, _) = Pubkey::find_program_address(
-                &[CONFIG_SEED],
-                &crate::ID,
-            );
-            require!(
-                config.key() == expected_config_key,
-                EscrowError::InvalidState
-            );
-
-            // Validate fee_collector PDA
-            let (expected_fee_collector, _) = Pubkey::find_program_address(
-                &[FEE_COLLECTOR_SEED],
-                &crate::ID,
-            );
-            require!(
-                fee_collector.key() == expected_fee_collector,
-                EscrowError::InvalidFeeCollector
-            );
-
-            // Calculate fee (basis points: 100 = 1%)
-            fee_amount = amount
-                .checked_mul(config.fee_basis_points as u64)
-                .ok_or(EscrowError::InsufficientFunds)?
-                .checked_div(10_000)
-                .ok_or(EscrowError::InsufficientFunds)?;
-            
-            seller_amount = amount
-                .checked_sub(fee_amount)
-                .ok_or(EscrowError::InsufficientFunds)?;
-
-            // Transfer fee to fee collector
-            **escrow.to_account_info().try_borrow_mut_lamports()? -= fee_amount;
-            **fee_collector.try_borrow_mut_lamports()? += fee_amount;
-
-            msg!("Platform fee deducted: {} lamports ({}%)", 
-                fee_amount, 
-                config.fee_basis_points as f64 / 100.0
-            );
-        }
-    }
-
-    // Transfer remaining funds from escrow PDA to seller
-    **escrow.to_account_info().try_borrow_mut_lamports()? = escrow
-        .to_account_info()
-        .lamports()
-        .checked_sub(seller_amount)
+    let token_vault = TokenVault::from_accounts(
+        &ctx.accounts.mint,
+        &ctx.accounts.vault,
+        &ctx.accounts.token_program,
+    );
+
+    // Release the next milestone in order
+    let (milestone_index, amount) = escrow
+        .next_milestone()
+        .ok_or(EscrowError::InvalidState)?;
+    let config = &ctx.accounts.config;
+
+    // Calculate fee on this milestone at the seller's tier (basis points: 100 = 1%)
+    let fee_basis_points = config.seller_fee_basis_points(&ctx.accounts.seller_reputation);
+    let fee_amount = config
+        .calculate_seller_fee(amount, &ctx.accounts.seller_reputation)
         .ok_or(EscrowError::InsufficientFunds)?;
-    
-    **seller.to_account_info().try_borrow_mut_lamports()? = seller
-        .lamports()
-        .checked_add(seller_amount)
+    let seller_amount = amount
+        .checked_sub(fee_amount)
         .ok_or(EscrowError::InsufficientFunds)?;
 
-    // Update escrow status
-    escrow.status = EscrowStatus::Completed;
-
-    // Update reputation for buyer if account exists
-    if let Some(buyer_reputation) = &mut ctx.accounts.buyer_reputation {
-        buyer_reputation.increment_successful();
-        emit!(ReputationUpdated {
-            user: buyer_reputation.user,
-            successful_trades: buyer_reputation.successful_trades,
-            failed_trades: buyer_reputation.failed_trades,
-        });
-        msg!("Buyer reputation updated: {} successful trades", buyer_reputation.successful_trades);
-    }
-
-    // Update reputation for seller if account exists
-    if let Some(seller_reputation) = &mut ctx.accounts.seller_reputation {
-        seller_reputation.increment_successful();
-        emit!(ReputationUpdated {
-            user: seller_reputation.user,
-            successful_trades: seller_reputation.successful_trades,
-            failed_trades: seller_reputation.failed_trades,
-        });
-        msg!("Seller reputation updated: {} successful trades", seller_reputation.successful_trades);
-    }
+    // Transfer fee to fee collector
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        &ctx.accounts.fee_collector,
+        ctx.accounts.fee_collector_token_account.as_ref(),
+        fee_amount,
+    )?;
+
+    msg!("Platform fee deducted: {} ({}%)", fee_amount, fee_basis_points as f64 / 100.0);
+
+    // Transfer remaining funds from escrow to seller
+    pay_from_escrow(
+        escrow,
+        token_vault.as_ref(),
+        seller,
+        ctx.accounts.seller_token_account.as_ref(),
+        seller_amount,
+    )?;
+
+    // Record the released milestone
+    escrow.released_amount = escrow
+        .released_amount
+        .checked_add(amount)
+        .ok_or(EscrowError::Overflow)?;
+    escrow.milestones_released = escrow
+        .milestones_released
+        .checked_add(1)
+        .ok_or(EscrowError::Overflow)?;
 
     // Emit event
     emit!(FundsReleased {
         escrow: escrow.key(),
         seller: seller.key(),
+        milestone_index,
         amount: seller_amount,
         fee_amount,
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("Funds released: {} lamports to seller, {} lamports platform fee", seller_amount, fee_amount);
+    msg!(
+        "Milestone {} released: {} to seller, {} platform fee",
+        milestone_index,
+        seller_amount,
+        fee_amount
+    );
+
+    // The trade completes once every milestone has been released
+    if !escrow.all_milestones_released() {
+        return Ok(());
+    }
+
+    // Update escrow status and return the rent to the buyer
+    escrow.status = EscrowStatus::Completed;
+    let volume = escrow.lamport_volume(escrow.amount);
+    close_escrow(escrow, token_vault.as_ref(), &ctx.accounts.buyer.to_account_info())?;
+
+    // Both parties completed a successful trade
+    let outcome = || TradeOutcome { successful: true, volume, dispute: None };
+    record_trade_outcome(&mut ctx.accounts.buyer_reputation, outcome(), clock.unix_timestamp, "Buyer");
+    record_trade_outcome(&mut ctx.accounts.seller_reputation, outcome(), clock.unix_timestamp, "Seller");
+
+    msg!("All milestones released, escrow completed");
 
     Ok(())
 }
@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::{ARBITER_SEED, CONFIG_SEED},
     errors::EscrowError,
+    events::ArbiterRemoved,
     state::{Arbiter, Config},
 };
 
@@ -37,6 +38,12 @@ pub fn handler(ctx: Context<RemoveArbiter>) -> Result<()> {
     // This preserves the history while preventing them from resolving disputes
     arbiter_account.is_active = false;
 
+    emit!(ArbiterRemoved {
+        arbiter: arbiter_account.arbiter,
+        removed_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
     msg!("Arbiter removed: {}", arbiter_account.arbiter);
     msg!("Deactivated by admin: {}", ctx.accounts.admin.key());
 
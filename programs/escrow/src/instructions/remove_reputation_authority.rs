@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, REPUTATION_AUTHORITY_SEED},
+    errors::EscrowError,
+    events::ReputationAuthorityRemoved,
+    state::{Config, ReputationAuthority},
+};
+
+#[derive(Accounts)]
+pub struct RemoveReputationAuthority<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        close = admin,
+        seeds = [REPUTATION_AUTHORITY_SEED, authority.key().as_ref()],
+        bump = reputation_authority.bump,
+    )]
+    pub reputation_authority: Account<'info, ReputationAuthority>,
+
+    /// The authority being revoked
+    /// CHECK: Used for PDA derivation
+    pub authority: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RemoveReputationAuthority>) -> Result<()> {
+    emit!(ReputationAuthorityRemoved {
+        authority: ctx.accounts.authority.key(),
+        removed_by: ctx.accounts.admin.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Reputation authority removed: {}", ctx.accounts.authority.key());
+    msg!("Removed by admin: {}", ctx.accounts.admin.key());
+
+    Ok(())
+}
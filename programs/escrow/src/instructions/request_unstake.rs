@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_SEED, CONFIG_SEED},
+    errors::EscrowError,
+    events::UnstakeRequested,
+    state::{Arbiter, Config},
+};
+
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = arbiter_account.stake > 0 @ EscrowError::NoStake,
+        constraint = arbiter_account.pending_rulings == 0 @ EscrowError::PendingRulings,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub arbiter: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<RequestUnstake>) -> Result<()> {
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+    let clock = Clock::get()?;
+
+    // The arbiter stops taking rulings now, the stake stays slashable during the cooldown.
+    // Rulings still in their appeal window must be executed first, they can be overturned.
+    arbiter_account.unstake_requested_at = clock.unix_timestamp;
+
+    let withdrawable_at = clock
+        .unix_timestamp
+        .saturating_add(ctx.accounts.config.stake_cooldown as i64);
+
+    emit!(UnstakeRequested {
+        arbiter: arbiter_account.arbiter,
+        stake: arbiter_account.stake,
+        withdrawable_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Arbiter {} can withdraw {} lamports after {}",
+        arbiter_account.arbiter,
+        arbiter_account.stake,
+        withdrawable_at
+    );
+
+    Ok(())
+}
@@ -3,218 +3,170 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::*,
     errors::EscrowError,
-    events::{DisputeResolved, ReputationUpdated},
-    state::{Arbiter, Escrow, EscrowStatus, Reputation},
+    events::{DisputeRuled, DisputeVoteTallied},
+    state::{Arbiter, Config, DisputeResolution, DisputeVote, Escrow, EscrowStatus},
+    utils::update_panel_arbiters,
 };
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum DisputeResolution {
-    FavorBuyer,   // Refund the buyer
-    FavorSeller,  // Pay the seller
-    Split,        // Split funds 50/50
-}
-
 #[derive(Accounts)]
 pub struct ResolveDispute<'info> {
     #[account(
         mut,
-        seeds = [ESCROW_SEED, buyer.key().as_ref(), seller.key().as_ref()],
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
         bump = escrow.bump,
         constraint = escrow.status == EscrowStatus::Disputed @ EscrowError::InvalidState,
+        constraint = escrow.is_assigned_arbiter(&arbiter.key()) @ EscrowError::ArbiterNotAssigned,
     )]
     pub escrow: Account<'info, Escrow>,
 
-    /// The arbiter who resolves disputes
+    /// The arbiter who rules on the dispute
     pub arbiter: Signer<'info>,
 
-    /// The arbiter's authorization account
+    /// The arbiter's authorization account, where the ruling is added to their statistics
     #[account(
+        mut,
         seeds = [ARBITER_SEED, arbiter.key().as_ref()],
         bump = arbiter_account.bump,
-        constraint = arbiter_account.can_resolve_disputes() @ EscrowError::UnauthorizedArbiter,
+        constraint = arbiter_account.can_resolve_disputes(config.min_arbiter_stake) @ EscrowError::UnauthorizedArbiter,
     )]
     pub arbiter_account: Account<'info, Arbiter>,
 
-    /// CHECK: Buyer account for refund
-    #[account(mut)]
-    pub buyer: AccountInfo<'info>,
-
-    /// CHECK: Seller account for payment
-    #[account(mut)]
-    pub seller: AccountInfo<'info>,
-
-    /// Buyer's reputation account (optional)
+    /// Panel votes (panel disputes only)
     #[account(
-        mut,
-        seeds = [REPUTATION_SEED, buyer.key().as_ref()],
-        bump,
+        seeds = [DISPUTE_VOTE_SEED, escrow.key().as_ref()],
+        bump = dispute_vote.bump,
     )]
-    pub buyer_reputation: Option<Account<'info, Reputation>>,
+    pub dispute_vote: Option<Account<'info, DisputeVote>>,
 
-    /// Seller's reputation account (optional)
     #[account(
-        mut,
-        seeds = [REPUTATION_SEED, seller.key().as_ref()],
-        bump,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
     )]
-    pub seller_reputation: Option<Account<'info, Reputation>>,
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Used for PDA derivation
+    pub buyer: AccountInfo<'info>,
 
-    pub system_program: Program<'info, System>,
+    /// CHECK: Used for PDA derivation
+    pub seller: AccountInfo<'info>,
 }
 
-pub fn handler(ctx: Context<ResolveDispute>, resolution: DisputeResolution) -> Result<()> {
+/// Rules on a dispute. The ruling is held as pending until `execute_ruling` carries it out,
+/// once the appeal window has closed or an appeal against it has been decided.
+/// For panel disputes `resolution` must match the panel's decision, and the arbiter accounts
+/// of the panel members are passed as remaining accounts, in the order they voted.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+    resolution: DisputeResolution,
+) -> Result<()> {
     let escrow = &mut ctx.accounts.escrow;
-    let buyer = &ctx.accounts.buyer;
-    let seller = &ctx.accounts.seller;
+    let arbiter = ctx.accounts.arbiter.key();
     let clock = Clock::get()?;
-
-    let amount = escrow.amount;
-
-    match resolution {
-        DisputeResolution::FavorBuyer => {
-            // Refund full amount to buyer
-            **escrow.to_account_info().try_borrow_mut_lamports()? = escrow
-                .to_account_info()
-                .lamports()
-                .checked_sub(amount)
-                .ok_or(EscrowError::InsufficientFunds)?;
-
-            **buyer.to_account_info().try_borrow_mut_lamports()? = buyer
-                .lamports()
-                .checked_add(amount)
-                .ok_or(EscrowError::Overflow)?;
-
-            msg!("Dispute resolved in favor of buyer: {} lamports refunded", amount);
-        }
-        DisputeResolution::FavorSeller => {
-            // Pay full amount to seller
-            **escrow.to_account_info().try_borrow_mut_lamports()? = escrow
-                .to_account_info()
-                .lamports()
-                .checked_sub(amount)
-                .ok_or(EscrowError::InsufficientFunds)?;
-
-            **seller.to_account_info().try_borrow_mut_lamports()? = seller
-                .lamports()
-                .checked_add(amount)
-                .ok_or(EscrowError::Overflow)?;
-
-            msg!("Dispute resolved in favor of seller: {} lamports paid", amount);
-        }
-        DisputeResolution::Split => {
-            // Split funds 50/50
-            let half_amount = amount
-                .checked_div(2)
-                .ok_or(EscrowError::Overflow)?;
-            let remainder = amount
-                .checked_sub(half_amount)
-                .ok_or(EscrowError::Overflow)?;
-
-            **escrow.to_account_info().try_borrow_mut_lamports()? = escrow
-                .to_account_info()
-                .lamports()
-                .checked_sub(amount)
-                .ok_or(EscrowError::InsufficientFunds)?;
-
-            **buyer.to_account_info().try_borrow_mut_lamports()? = buyer
-                .lamports()
-                .checked_add(half_amount)
-                .ok_or(EscrowError::Overflow)?;
-
-            **seller.to_account_info().try_borrow_mut_lamports()? = seller
-                .lamports()
-                .checked_add(remainder)
-                .ok_or(EscrowError::Overflow)?;
-
-            msg!(
-                "Dispute resolved with split: {} lamports to buyer, {} lamports to seller",
-                half_amount,
-                remainder
+    let resolution_time = clock.unix_timestamp.saturating_sub(escrow.disputed_at);
+
+    require!(
+        resolution.buyer_basis_points().is_some(),
+        EscrowError::InvalidSplit
+    );
+
+    if let Some(appeal) = &escrow.appeal {
+        // Appeals are heard by an arbiter who had no part in the appealed ruling
+        require!(
+            appeal.original_arbiter != arbiter,
+            EscrowError::AppealArbiterConflict
+        );
+        if escrow.is_panel_dispute() {
+            let dispute_vote = ctx
+                .accounts
+                .dispute_vote
+                .as_ref()
+                .ok_or(EscrowError::InvalidPanelAccounts)?;
+            require!(
+                !dispute_vote.has_voted(&arbiter),
+                EscrowError::AppealArbiterConflict
             );
         }
+    } else if escrow.is_panel_dispute() {
+        // Panel disputes carry out the vote instead of a single arbiter's decision
+        let dispute_vote = ctx
+            .accounts
+            .dispute_vote
+            .as_ref()
+            .ok_or(EscrowError::VotingIncomplete)?;
+        let deadline_passed = clock.unix_timestamp >= escrow.voting_deadline;
+        let (decided, votes_for) = dispute_vote
+            .tally(escrow.panel_size, deadline_passed)
+            .ok_or(EscrowError::VotingIncomplete)?;
+        require!(decided == resolution, EscrowError::ResolutionMismatch);
+
+        // The ruling is credited to the panel members who voted, whoever submits it
+        update_panel_arbiters(
+            &dispute_vote.votes,
+            ctx.remaining_accounts,
+            Some(&mut ctx.accounts.arbiter_account),
+            |_, arbiter_account| {
+                arbiter_account.record_decision(&resolution, resolution_time, clock.unix_timestamp)
+            },
+        )?;
+
+        emit!(DisputeVoteTallied {
+            escrow: escrow.key(),
+            resolution,
+            votes_for,
+            votes_cast: dispute_vote.votes.len() as u8,
+            panel_size: escrow.panel_size,
+            deadline_passed,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
-    // Update escrow status to Completed
-    escrow.status = EscrowStatus::Completed;
-
-    // Update reputations based on resolution
-    match resolution {
-        DisputeResolution::FavorBuyer => {
-            // Buyer wins: buyer successful, seller failed
-            if let Some(buyer_reputation) = &mut ctx.accounts.buyer_reputation {
-                buyer_reputation.increment_successful();
-                emit!(ReputationUpdated {
-                    user: buyer_reputation.user,
-                    successful_trades: buyer_reputation.successful_trades,
-                    failed_trades: buyer_reputation.failed_trades,
-                });
-                msg!("Buyer reputation updated: {} successful trades", buyer_reputation.successful_trades);
-            }
-            if let Some(seller_reputation) = &mut ctx.accounts.seller_reputation {
-                seller_reputation.increment_failed();
-                emit!(ReputationUpdated {
-                    user: seller_reputation.user,
-                    successful_trades: seller_reputation.successful_trades,
-                    failed_trades: seller_reputation.failed_trades,
-                });
-                msg!("Seller reputation updated: {} failed trades", seller_reputation.failed_trades);
-            }
-        }
-        DisputeResolution::FavorSeller => {
-            // Seller wins: seller successful, buyer failed
-            if let Some(seller_reputation) = &mut ctx.accounts.seller_reputation {
-                seller_reputation.increment_successful();
-                emit!(ReputationUpdated {
-                    user: seller_reputation.user,
-                    successful_trades: seller_reputation.successful_trades,
-                    failed_trades: seller_reputation.failed_trades,
-                });
-                msg!("Seller reputation updated: {} successful trades", seller_reputation.successful_trades);
-            }
-            if let Some(buyer_reputation) = &mut ctx.accounts.buyer_reputation {
-                buyer_reputation.increment_failed();
-                emit!(ReputationUpdated {
-                    user: buyer_reputation.user,
-                    successful_trades: buyer_reputation.successful_trades,
-                    failed_trades: buyer_reputation.failed_trades,
-                });
-                msg!("Buyer reputation updated: {} failed trades", buyer_reputation.failed_trades);
-            }
-        }
-        DisputeResolution::Split => {
-            // Split resolution: both parties share responsibility (both get failed trade)
-            if let Some(buyer_reputation) = &mut ctx.accounts.buyer_reputation {
-                buyer_reputation.increment_failed();
-                emit!(ReputationUpdated {
-                    user: buyer_reputation.user,
-                    successful_trades: buyer_reputation.successful_trades,
-                    failed_trades: buyer_reputation.failed_trades,
-                });
-                msg!("Buyer reputation updated: {} failed trades", buyer_reputation.failed_trades);
-            }
-            if let Some(seller_reputation) = &mut ctx.accounts.seller_reputation {
-                seller_reputation.increment_failed();
-                emit!(ReputationUpdated {
-                    user: seller_reputation.user,
-                    successful_trades: seller_reputation.successful_trades,
-                    failed_trades: seller_reputation.failed_trades,
-                });
-                msg!("Seller reputation updated: {} failed trades", seller_reputation.failed_trades);
-            }
-        }
+    // An appeal ruling is final, any other ruling can be appealed within the window
+    let is_appeal = escrow.appeal.is_some();
+    let is_panel_decision = !is_appeal && escrow.is_panel_dispute();
+    escrow.pending_resolution = Some(resolution);
+    escrow.ruled_by = Some(arbiter);
+    escrow.ruling_at = clock.unix_timestamp;
+    escrow.appeal_deadline = if is_appeal {
+        0
+    } else {
+        clock
+            .unix_timestamp
+            .checked_add(ctx.accounts.config.appeal_window as i64)
+            .ok_or(EscrowError::Overflow)?
+    };
+    escrow.status = EscrowStatus::Ruled;
+
+    // The submitter has to see the ruling executed either way
+    if is_panel_decision {
+        ctx.accounts.arbiter_account.add_pending_ruling();
+    } else {
+        ctx.accounts.arbiter_account.record_ruling(
+            &resolution,
+            resolution_time,
+            clock.unix_timestamp,
+        );
     }
 
-    // Emit event
-    emit!(DisputeResolved {
+    emit!(DisputeRuled {
         escrow: escrow.key(),
-        arbiter: ctx.accounts.arbiter.key(),
-        resolution: match resolution {
-            DisputeResolution::FavorBuyer => "FavorBuyer".to_string(),
-            DisputeResolution::FavorSeller => "FavorSeller".to_string(),
-            DisputeResolution::Split => "Split".to_string(),
-        },
+        arbiter,
+        resolution,
+        is_appeal,
+        appeal_deadline: escrow.appeal_deadline,
         timestamp: clock.unix_timestamp,
     });
 
+    msg!(
+        "Dispute ruled by {}, executable after {}",
+        arbiter,
+        escrow.appeal_deadline
+    );
+
     Ok(())
 }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, MAX_FEE_TIERS},
+    errors::EscrowError,
+    events::FeeTiersUpdated,
+    instructions::initialize_config::ConfigError,
+    state::{Config, FeeTier},
+};
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    require!(fee_tiers.len() <= MAX_FEE_TIERS, EscrowError::InvalidFeeTiers);
+    for tier in &fee_tiers {
+        require!(tier.fee_basis_points <= 1000, ConfigError::FeeTooHigh);
+        require!(tier.min_success_rate_bps <= 10_000, EscrowError::InvalidFeeTiers);
+    }
+
+    // Replaces the whole schedule, an empty list removes all discounts
+    config.fee_tiers = fee_tiers.clone();
+
+    emit!(FeeTiersUpdated {
+        admin: ctx.accounts.admin.key(),
+        fee_tiers,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Fee tiers updated: {} tiers", config.fee_tiers.len());
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::CONFIG_SEED,
+    errors::EscrowError,
+    events::GuardianUpdated,
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_guardian = config.guardian;
+
+    config.guardian = guardian;
+
+    emit!(GuardianUpdated {
+        admin: ctx.accounts.admin.key(),
+        old_guardian,
+        new_guardian: guardian,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!("Guardian changed from {} to {}", old_guardian, guardian);
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::CONFIG_SEED,
+    errors::EscrowError,
+    events::PauseUpdated,
+    state::Config,
+};
+
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.can_pause(&authority.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The admin or the guardian
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetPause>, escrows_paused: bool, emergency_mode: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.escrows_paused = escrows_paused;
+    config.emergency_mode = emergency_mode;
+
+    emit!(PauseUpdated {
+        authority: ctx.accounts.authority.key(),
+        escrows_paused,
+        emergency_mode,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    msg!(
+        "Pause flags set by {}: escrows_paused={}, emergency_mode={}",
+        ctx.accounts.authority.key(),
+        escrows_paused,
+        emergency_mode
+    );
+
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ARBITER_SEED, CONFIG_SEED, MAX_SLASH_REASON_LEN, STAKE_VAULT_SEED},
+    errors::EscrowError,
+    state::{Arbiter, Config},
+    utils::slash_stake,
+};
+
+#[derive(Accounts)]
+pub struct SlashArbiter<'info> {
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = arbiter_account.stake > 0 @ EscrowError::NoStake,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// Stake vault PDA holding the arbiter's stake
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.stake_vault_bump,
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    /// The arbiter being slashed
+    /// CHECK: Used for PDA derivation
+    pub arbiter: AccountInfo<'info>,
+
+    /// The harmed party or the fee collector
+    /// CHECK: Any account chosen by the admin
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Slashes up to `amount` of an arbiter's stake for misconduct the admin has established
+pub fn handler(ctx: Context<SlashArbiter>, amount: u64, reason: String) -> Result<()> {
+    require!(amount > 0, EscrowError::InvalidAmount);
+    require!(
+        !reason.is_empty() && reason.len() <= MAX_SLASH_REASON_LEN,
+        EscrowError::InvalidReason
+    );
+
+    slash_stake(
+        &mut ctx.accounts.arbiter_account,
+        &ctx.accounts.stake_vault.to_account_info(),
+        &ctx.accounts.recipient,
+        &ctx.accounts.system_program.to_account_info(),
+        amount,
+        ctx.accounts.admin.key(),
+        reason,
+    )?;
+
+    Ok(())
+}
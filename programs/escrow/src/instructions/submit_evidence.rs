@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::*,
+    errors::EscrowError,
+    events::EvidenceSubmitted,
+    state::{Arbiter, Config, Escrow, EscrowStatus, Evidence, EvidenceCategory},
+};
+
+#[derive(Accounts)]
+pub struct SubmitEvidence<'info> {
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            buyer.key().as_ref(),
+            seller.key().as_ref(),
+            escrow.escrow_id.to_le_bytes().as_ref(),
+        ],
+        bump = escrow.bump,
+        constraint = escrow.status == EscrowStatus::Disputed @ EscrowError::InvalidState,
+    )]
+    pub escrow: Account<'info, Escrow>,
+
+    /// Keyed by the escrow's creation time as well, so an escrow id reused after settlement
+    /// starts with fresh evidence
+    #[account(
+        init,
+        payer = submitter,
+        space = 8 + Evidence::LEN,
+        seeds = [
+            EVIDENCE_SEED,
+            escrow.key().as_ref(),
+            escrow.created_at.to_le_bytes().as_ref(),
+            escrow.evidence_count.to_le_bytes().as_ref(),
+        ],
+        bump
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    /// Buyer, seller or an arbiter able to rule on the escrow
+    #[account(mut)]
+    pub submitter: Signer<'info>,
+
+    /// Submitter's arbiter account (required when an arbiter submits)
+    #[account(
+        seeds = [ARBITER_SEED, submitter.key().as_ref()],
+        bump = arbiter_account.bump,
+    )]
+    pub arbiter_account: Option<Account<'info, Arbiter>>,
+
+    /// CHECK: Used for PDA derivation
+    pub buyer: AccountInfo<'info>,
+
+    /// CHECK: Used for PDA derivation
+    pub seller: AccountInfo<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = !config.emergency_mode @ EscrowError::EmergencyMode,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SubmitEvidence>,
+    content_hash: [u8; 32],
+    uri: String,
+    category: EvidenceCategory,
+) -> Result<()> {
+    let escrow = &mut ctx.accounts.escrow;
+    let evidence = &mut ctx.accounts.evidence;
+    let submitter = ctx.accounts.submitter.key();
+    let clock = Clock::get()?;
+
+    require!(uri.len() <= MAX_EVIDENCE_URI_LEN, EscrowError::EvidenceUriTooLong);
+
+    // Parties can always submit, arbiters only if they could rule on this escrow
+    let is_party = escrow.buyer == submitter || escrow.seller == submitter;
+    let min_stake = ctx.accounts.config.min_arbiter_stake;
+    let is_arbiter = ctx
+        .accounts
+        .arbiter_account
+        .as_ref()
+        .is_some_and(|arbiter| arbiter.can_resolve_disputes(min_stake))
+        && escrow.is_assigned_arbiter(&submitter);
+    require!(is_party || is_arbiter, EscrowError::Unauthorized);
+
+    let index = escrow.evidence_count;
+
+    // Initialize evidence account
+    evidence.escrow = escrow.key();
+    evidence.submitted_by = submitter;
+    evidence.index = index;
+    evidence.content_hash = content_hash;
+    evidence.uri = uri;
+    evidence.category = category;
+    evidence.submitted_at = clock.unix_timestamp;
+    evidence.bump = ctx.bumps.evidence;
+
+    escrow.evidence_count = index
+        .checked_add(1)
+        .ok_or(EscrowError::Overflow)?;
+
+    emit!(EvidenceSubmitted {
+        escrow: escrow.key(),
+        evidence: evidence.key(),
+        submitted_by: submitter,
+        index,
+        category,
+        content_hash,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Evidence #{} submitted for escrow by {}", index, submitter);
+
+    Ok(())
+}
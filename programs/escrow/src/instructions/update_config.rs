@@ -0,0 +1,210 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, MAX_ESCROW_ARBITERS, MAX_PANEL_SIZE, MIN_PANEL_SIZE},
+    errors::EscrowError,
+    events::ConfigUpdated,
+    instructions::initialize_config::ConfigError,
+    state::Config,
+};
+
+/// New values for the config, `None` leaves a setting unchanged
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct ConfigUpdate {
+    pub fee_basis_points: Option<u16>,
+    pub arbiter_fee_basis_points: Option<u16>,
+    pub min_arbiter_fee: Option<u64>,
+    pub buyer_dispute_fee_basis_points: Option<u16>,
+    pub seller_dispute_fee_basis_points: Option<u16>,
+    pub split_dispute_fee_basis_points: Option<u16>,
+    pub panel_threshold: Option<u64>,
+    pub panel_size: Option<u8>,
+    pub voting_period: Option<u32>,
+    pub appeal_bond: Option<u64>,
+    pub appeal_window: Option<u32>,
+    pub min_arbiter_stake: Option<u64>,
+    pub stake_cooldown: Option<u32>,
+    pub overturn_slash: Option<u64>,
+    pub dispute_window: Option<u32>,
+    pub escrow_timeout: Option<u32>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        constraint = config.is_admin(&admin.key()) @ EscrowError::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let admin = ctx.accounts.admin.key();
+    let timestamp = Clock::get()?.unix_timestamp;
+
+    // Same limits as initialize_config (max 10% = 1000 basis points)
+    let platform_fees = [
+        update.fee_basis_points,
+        update.buyer_dispute_fee_basis_points,
+        update.seller_dispute_fee_basis_points,
+        update.split_dispute_fee_basis_points,
+    ];
+    for fee_basis_points in platform_fees.into_iter().flatten() {
+        require!(fee_basis_points <= 1000, ConfigError::FeeTooHigh);
+    }
+    if let Some(arbiter_fee_basis_points) = update.arbiter_fee_basis_points {
+        require!(
+            arbiter_fee_basis_points <= 1000,
+            ConfigError::ArbiterFeeTooHigh
+        );
+    }
+    if let Some(panel_size) = update.panel_size {
+        require!(
+            panel_size % 2 == 1
+                && (MIN_PANEL_SIZE..=MAX_PANEL_SIZE).contains(&(panel_size as usize))
+                && panel_size as usize <= MAX_ESCROW_ARBITERS,
+            ConfigError::InvalidPanelSize
+        );
+    }
+    // A seller can't claim the funds while the buyer may still dispute
+    require!(
+        update.dispute_window.unwrap_or(config.dispute_window)
+            < update.escrow_timeout.unwrap_or(config.escrow_timeout),
+        ConfigError::InvalidEscrowTimeout
+    );
+
+    apply_update(
+        &mut config.fee_basis_points,
+        update.fee_basis_points,
+        "fee_basis_points",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.arbiter_fee_basis_points,
+        update.arbiter_fee_basis_points,
+        "arbiter_fee_basis_points",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.min_arbiter_fee,
+        update.min_arbiter_fee,
+        "min_arbiter_fee",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.buyer_dispute_fee_basis_points,
+        update.buyer_dispute_fee_basis_points,
+        "buyer_dispute_fee_basis_points",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.seller_dispute_fee_basis_points,
+        update.seller_dispute_fee_basis_points,
+        "seller_dispute_fee_basis_points",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.split_dispute_fee_basis_points,
+        update.split_dispute_fee_basis_points,
+        "split_dispute_fee_basis_points",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.panel_threshold,
+        update.panel_threshold,
+        "panel_threshold",
+        admin,
+        timestamp,
+    );
+    apply_update(&mut config.panel_size, update.panel_size, "panel_size", admin, timestamp);
+    apply_update(
+        &mut config.voting_period,
+        update.voting_period,
+        "voting_period",
+        admin,
+        timestamp,
+    );
+    apply_update(&mut config.appeal_bond, update.appeal_bond, "appeal_bond", admin, timestamp);
+    apply_update(
+        &mut config.appeal_window,
+        update.appeal_window,
+        "appeal_window",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.min_arbiter_stake,
+        update.min_arbiter_stake,
+        "min_arbiter_stake",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.stake_cooldown,
+        update.stake_cooldown,
+        "stake_cooldown",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.overturn_slash,
+        update.overturn_slash,
+        "overturn_slash",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.dispute_window,
+        update.dispute_window,
+        "dispute_window",
+        admin,
+        timestamp,
+    );
+    apply_update(
+        &mut config.escrow_timeout,
+        update.escrow_timeout,
+        "escrow_timeout",
+        admin,
+        timestamp,
+    );
+
+    msg!("Config updated by admin: {}", admin);
+
+    Ok(())
+}
+
+/// Writes `value` into `field` and emits the change for monitoring
+fn apply_update<T: Copy + Into<u64>>(
+    field: &mut T,
+    value: Option<T>,
+    parameter: &str,
+    admin: Pubkey,
+    timestamp: i64,
+) {
+    let Some(value) = value else {
+        return;
+    };
+
+    let old_value = *field;
+    *field = value;
+
+    emit!(ConfigUpdated {
+        admin,
+        parameter: parameter.to_string(),
+        old_value: old_value.into(),
+        new_value: value.into(),
+        timestamp,
+    });
+    msg!("{}: {} -> {}", parameter, old_value.into(), value.into());
+}
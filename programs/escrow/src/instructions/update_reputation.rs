@@ -1,17 +1,12 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constants::REPUTATION_SEED,
-    events::ReputationUpdated,
-    state::Reputation,
+    constants::{CONFIG_SEED, MAX_REPUTATION_REASON_LEN, REPUTATION_AUTHORITY_SEED, REPUTATION_SEED},
+    errors::EscrowError,
+    events::{ReputationAdjusted, ReputationUpdated},
+    state::{Config, Reputation, ReputationAuthority, ReputationUpdate},
 };
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum ReputationUpdate {
-    Successful,
-    Failed,
-}
-
 #[derive(Accounts)]
 pub struct UpdateReputation<'info> {
     #[account(
@@ -26,13 +21,38 @@ pub struct UpdateReputation<'info> {
     /// CHECK: This can be any valid account
     pub user: AccountInfo<'info>,
 
-    /// The authority calling this update
-    /// Note: In production, this should be restricted to program PDAs or authorized signers
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Registry entry for the authority (omit when the admin adjusts directly)
+    #[account(
+        seeds = [REPUTATION_AUTHORITY_SEED, authority.key().as_ref()],
+        bump = reputation_authority.bump,
+    )]
+    pub reputation_authority: Option<Account<'info, ReputationAuthority>>,
+
+    /// The config admin, or an approved authority such as a PDA another program
+    /// signs with when calling in via CPI
     pub authority: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<UpdateReputation>, update: ReputationUpdate) -> Result<()> {
+pub fn handler(ctx: Context<UpdateReputation>, update: ReputationUpdate, reason: String) -> Result<()> {
     let reputation = &mut ctx.accounts.reputation;
+    let authority = &ctx.accounts.authority;
+
+    // Only the admin or a registered reputation authority may adjust reputation
+    require!(
+        ctx.accounts.config.is_admin(&authority.key())
+            || ctx.accounts.reputation_authority.is_some(),
+        EscrowError::Unauthorized
+    );
+    require!(
+        !reason.is_empty() && reason.len() <= MAX_REPUTATION_REASON_LEN,
+        EscrowError::InvalidReason
+    );
 
     // Update reputation based on the update type
     match update {
@@ -45,10 +65,15 @@ pub fn handler(ctx: Context<UpdateReputation>, update: ReputationUpdate) -> Resu
     }
 
     // Emit reputation updated event
-    emit!(ReputationUpdated {
+    emit!(ReputationUpdated::new(reputation));
+
+    // Record the manual adjustment separately from trade settlements
+    emit!(ReputationAdjusted {
         user: reputation.user,
-        successful_trades: reputation.successful_trades,
-        failed_trades: reputation.failed_trades,
+        authority: authority.key(),
+        update,
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
     });
 
     Ok(())
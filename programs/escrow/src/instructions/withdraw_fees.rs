@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
 
 use crate::{
     constants::*,
@@ -27,6 +30,30 @@ pub struct WithdrawFees<'info> {
     )]
     pub fee_collector: AccountInfo<'info>,
 
+    /// Mint of the token fees being withdrawn (omit to withdraw SOL)
+    #[account(mint::token_program = token_program)]
+    pub mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Fee collector's token account holding the accumulated token fees
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fee_collector,
+        token::token_program = token_program,
+    )]
+    pub fee_collector_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Admin's token account receiving the withdrawn token fees
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = admin,
+        token::token_program = token_program,
+    )]
+    pub admin_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -35,12 +62,6 @@ pub fn handler(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
     let admin = &ctx.accounts.admin;
     let config = &ctx.accounts.config;
 
-    // Verify sufficient balance
-    require!(
-        fee_collector.lamports() >= amount,
-        EscrowError::InsufficientFunds
-    );
-
     // Create PDA signer seeds
     let fee_collector_seeds = &[
         FEE_COLLECTOR_SEED,
@@ -48,6 +69,54 @@ pub fn handler(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
     ];
     let signer_seeds = &[&fee_collector_seeds[..]];
 
+    if let Some(mint) = &ctx.accounts.mint {
+        let (Some(fee_collector_token_account), Some(admin_token_account), Some(token_program)) = (
+            &ctx.accounts.fee_collector_token_account,
+            &ctx.accounts.admin_token_account,
+            &ctx.accounts.token_program,
+        ) else {
+            return err!(EscrowError::MissingTokenAccounts);
+        };
+
+        // Verify sufficient balance
+        require!(
+            fee_collector_token_account.amount >= amount,
+            EscrowError::InsufficientFunds
+        );
+
+        // Transfer token fees with the fee collector PDA as authority
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: fee_collector_token_account.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: admin_token_account.to_account_info(),
+                    authority: fee_collector.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            mint.decimals,
+        )?;
+
+        msg!(
+            "Admin {} withdrew {} of mint {} from fee collector {}",
+            admin.key(),
+            amount,
+            mint.key(),
+            fee_collector.key()
+        );
+
+        return Ok(());
+    }
+
+    // Verify sufficient balance
+    require!(
+        fee_collector.lamports() >= amount,
+        EscrowError::InsufficientFunds
+    );
+
     // Transfer using system program CPI with PDA signer
     anchor_lang::system_program::transfer(
         CpiContext::new_with_signer(
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+use crate::{
+    constants::{ARBITER_SEED, CONFIG_SEED, STAKE_VAULT_SEED},
+    errors::EscrowError,
+    events::StakeWithdrawn,
+    state::{Arbiter, Config},
+};
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    #[account(
+        mut,
+        seeds = [ARBITER_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.bump,
+        constraint = arbiter_account.unstake_requested_at > 0 @ EscrowError::UnstakeNotRequested,
+        constraint = arbiter_account.pending_rulings == 0 @ EscrowError::PendingRulings,
+    )]
+    pub arbiter_account: Account<'info, Arbiter>,
+
+    /// Stake vault PDA holding the arbiter's stake
+    #[account(
+        mut,
+        seeds = [STAKE_VAULT_SEED, arbiter.key().as_ref()],
+        bump = arbiter_account.stake_vault_bump,
+    )]
+    pub stake_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<WithdrawStake>) -> Result<()> {
+    let arbiter_account = &mut ctx.accounts.arbiter_account;
+    let clock = Clock::get()?;
+
+    require!(
+        arbiter_account
+            .can_withdraw_stake(clock.unix_timestamp, ctx.accounts.config.stake_cooldown),
+        EscrowError::StakeCooldownActive
+    );
+
+    // Empty the whole vault, not just the recorded stake. Anyone can send lamports to it,
+    // and leaving a donation behind would fail the rent check on every withdrawal.
+    let amount = ctx.accounts.stake_vault.lamports();
+    let arbiter_key = arbiter_account.arbiter;
+    let vault_seeds = &[
+        STAKE_VAULT_SEED,
+        arbiter_key.as_ref(),
+        &[arbiter_account.stake_vault_bump],
+    ];
+    let signer_seeds = &[&vault_seeds[..]];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stake_vault.to_account_info(),
+                to: ctx.accounts.arbiter.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    arbiter_account.stake = 0;
+    arbiter_account.unstake_requested_at = 0;
+
+    emit!(StakeWithdrawn {
+        arbiter: arbiter_key,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "Arbiter {} withdrew {} lamports of stake",
+        arbiter_key,
+        amount
+    );
+
+    Ok(())
+}
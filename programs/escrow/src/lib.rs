@@ -5,6 +5,7 @@ mod errors;
 mod events;
 mod instructions;
 mod state;
+mod utils;
 
 pub use constants::*;
 pub use errors::*;
@@ -22,8 +23,26 @@ pub mod escrow {
         instructions::initialize::handler(ctx)
     }
 
-    pub fn create_escrow(ctx: Context<CreateEscrow>, amount: u64) -> Result<()> {
-        instructions::create_escrow::handler(ctx, amount)
+    pub fn create_escrow(
+        ctx: Context<CreateEscrow>,
+        escrow_id: u64,
+        amount: u64,
+        milestones: Vec<u64>,
+        arbiters: Vec<Pubkey>,
+        keep_open: bool,
+    ) -> Result<()> {
+        instructions::create_escrow::handler(
+            ctx,
+            escrow_id,
+            amount,
+            milestones,
+            arbiters,
+            keep_open,
+        )
+    }
+
+    pub fn accept_escrow(ctx: Context<AcceptEscrow>) -> Result<()> {
+        instructions::accept_escrow::handler(ctx)
     }
 
     pub fn release_funds(ctx: Context<ReleaseFunds>) -> Result<()> {
@@ -34,30 +53,118 @@ pub mod escrow {
         instructions::cancel_escrow::handler(ctx)
     }
 
+    pub fn close_escrow(ctx: Context<CloseEscrow>) -> Result<()> {
+        instructions::close_escrow::handler(ctx)
+    }
+
     pub fn refund_buyer(ctx: Context<RefundBuyer>) -> Result<()> {
         instructions::refund_buyer::handler(ctx)
     }
 
-    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
-        instructions::raise_dispute::handler(ctx)
+    pub fn claim_expired_escrow(ctx: Context<ClaimExpiredEscrow>) -> Result<()> {
+        instructions::claim_expired_escrow::handler(ctx)
+    }
+
+    pub fn raise_dispute(ctx: Context<RaiseDispute>, reason: DisputeReason) -> Result<()> {
+        instructions::raise_dispute::handler(ctx, reason)
     }
 
-    pub fn resolve_dispute(
-        ctx: Context<ResolveDispute>,
-        resolution: instructions::resolve_dispute::DisputeResolution,
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        content_hash: [u8; 32],
+        uri: String,
+        category: EvidenceCategory,
+    ) -> Result<()> {
+        instructions::submit_evidence::handler(ctx, content_hash, uri, category)
+    }
+
+    pub fn cast_dispute_vote(ctx: Context<CastDisputeVote>, resolution: DisputeResolution) -> Result<()> {
+        instructions::cast_dispute_vote::handler(ctx, resolution)
+    }
+
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        resolution: DisputeResolution,
     ) -> Result<()> {
         instructions::resolve_dispute::handler(ctx, resolution)
     }
 
+    pub fn appeal_ruling(ctx: Context<AppealRuling>) -> Result<()> {
+        instructions::appeal_ruling::handler(ctx)
+    }
+
+    pub fn execute_ruling<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteRuling<'info>>) -> Result<()> {
+        instructions::execute_ruling::handler(ctx)
+    }
+
     pub fn update_reputation(
         ctx: Context<UpdateReputation>,
-        update: instructions::update_reputation::ReputationUpdate,
+        update: ReputationUpdate,
+        reason: String,
+    ) -> Result<()> {
+        instructions::update_reputation::handler(ctx, update, reason)
+    }
+
+    pub fn migrate_reputation(ctx: Context<MigrateReputation>) -> Result<()> {
+        instructions::migrate_reputation::handler(ctx)
+    }
+
+    pub fn add_reputation_authority(ctx: Context<AddReputationAuthority>) -> Result<()> {
+        instructions::add_reputation_authority::handler(ctx)
+    }
+
+    pub fn remove_reputation_authority(ctx: Context<RemoveReputationAuthority>) -> Result<()> {
+        instructions::remove_reputation_authority::handler(ctx)
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        fee_basis_points: u16,
+        arbiter_fee_basis_points: u16,
+        min_arbiter_fee: u64,
+    ) -> Result<()> {
+        instructions::initialize_config::handler(
+            ctx,
+            fee_basis_points,
+            arbiter_fee_basis_points,
+            min_arbiter_fee,
+        )
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, update: ConfigUpdate) -> Result<()> {
+        instructions::update_config::handler(ctx, update)
+    }
+
+    pub fn migrate_config(
+        ctx: Context<MigrateConfig>,
+        arbiter_fee_basis_points: u16,
+        min_arbiter_fee: u64,
     ) -> Result<()> {
-        instructions::update_reputation::handler(ctx, update)
+        instructions::migrate_config::handler(ctx, arbiter_fee_basis_points, min_arbiter_fee)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::set_fee_tiers::handler(ctx, fee_tiers)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, new_admin: Pubkey) -> Result<()> {
+        instructions::propose_admin::handler(ctx, new_admin)
     }
 
-    pub fn initialize_config(ctx: Context<InitializeConfig>, fee_basis_points: u16) -> Result<()> {
-        instructions::initialize_config::handler(ctx, fee_basis_points)
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin::handler(ctx)
+    }
+
+    pub fn cancel_admin_transfer(ctx: Context<CancelAdminTransfer>) -> Result<()> {
+        instructions::cancel_admin_transfer::handler(ctx)
+    }
+
+    pub fn set_guardian(ctx: Context<SetGuardian>, guardian: Pubkey) -> Result<()> {
+        instructions::set_guardian::handler(ctx, guardian)
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, escrows_paused: bool, emergency_mode: bool) -> Result<()> {
+        instructions::set_pause::handler(ctx, escrows_paused, emergency_mode)
     }
 
     pub fn add_arbiter(ctx: Context<AddArbiter>) -> Result<()> {
@@ -68,6 +175,34 @@ pub mod escrow {
         instructions::remove_arbiter::handler(ctx)
     }
 
+    pub fn reactivate_arbiter(ctx: Context<ReactivateArbiter>) -> Result<()> {
+        instructions::reactivate_arbiter::handler(ctx)
+    }
+
+    pub fn migrate_arbiter(ctx: Context<MigrateArbiter>) -> Result<()> {
+        instructions::migrate_arbiter::handler(ctx)
+    }
+
+    pub fn close_arbiter(ctx: Context<CloseArbiter>) -> Result<()> {
+        instructions::close_arbiter::handler(ctx)
+    }
+
+    pub fn deposit_stake(ctx: Context<DepositStake>, amount: u64) -> Result<()> {
+        instructions::deposit_stake::handler(ctx, amount)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstake>) -> Result<()> {
+        instructions::request_unstake::handler(ctx)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>) -> Result<()> {
+        instructions::withdraw_stake::handler(ctx)
+    }
+
+    pub fn slash_arbiter(ctx: Context<SlashArbiter>, amount: u64, reason: String) -> Result<()> {
+        instructions::slash_arbiter::handler(ctx, amount, reason)
+    }
+
     pub fn withdraw_fees(ctx: Context<WithdrawFees>, amount: u64) -> Result<()> {
         instructions::withdraw_fees::handler(ctx, amount)
     }
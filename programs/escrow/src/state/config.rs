@@ -1,9 +1,51 @@
 use anchor_lang::prelude::*;
 
+use super::{DisputeResolution, Escrow, Reputation};
+use crate::constants::*;
+
+/// Discounted platform fee for sellers with a proven track record
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct FeeTier {
+    pub min_successful_trades: u64,
+    pub min_success_rate_bps: u16,  // 10000 = 100%
+    pub fee_basis_points: u16,
+}
+
+impl FeeTier {
+    pub const LEN: usize = 8  // min_successful_trades
+        + 2   // min_success_rate_bps
+        + 2;  // fee_basis_points
+
+    pub fn qualifies(&self, reputation: &Reputation) -> bool {
+        reputation.successful_trades >= self.min_successful_trades
+            && reputation.success_rate_bps() >= self.min_success_rate_bps
+    }
+}
+
 #[account]
 pub struct Config {
     pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,  // Proposed admin awaiting acceptance
+    pub guardian: Pubkey,  // Can toggle the pause flags alongside the admin
+    pub escrows_paused: bool,  // Blocks opening new escrows
+    pub emergency_mode: bool,  // Freezes settlement, only cancel_escrow stays open
     pub fee_basis_points: u16,  // Fee in basis points (e.g., 100 = 1%)
+    pub arbiter_fee_basis_points: u16,  // Paid to the resolving arbiter
+    pub min_arbiter_fee: u64,  // Flat minimum in lamports, SOL escrows only
+    pub buyer_dispute_fee_basis_points: u16,  // Platform fee when a dispute favors the buyer
+    pub seller_dispute_fee_basis_points: u16,  // Platform fee when a dispute favors the seller
+    pub split_dispute_fee_basis_points: u16,  // Platform fee when a dispute is split
+    pub fee_tiers: Vec<FeeTier>,  // Seller discounts on fee_basis_points
+    pub panel_threshold: u64,  // SOL escrows of at least this many lamports go to a panel, zero disables
+    pub panel_size: u8,  // Odd number of arbiters on a panel
+    pub voting_period: u32,  // Seconds the panel has to reach a majority
+    pub appeal_bond: u64,  // Lamports the appellant posts, forfeited to the winner if the ruling stands
+    pub appeal_window: u32,  // Seconds after a ruling during which it can be appealed, zero disables appeals
+    pub min_arbiter_stake: u64,  // Lamports an arbiter must have staked to rule on disputes
+    pub stake_cooldown: u32,  // Seconds between requesting an unstake and withdrawing
+    pub overturn_slash: u64,  // Lamports slashed from an arbiter whose ruling is overturned on appeal
+    pub dispute_window: u32,  // Seconds after acceptance during which a dispute can be raised
+    pub escrow_timeout: u32,  // Seconds after acceptance before the seller can claim unreleased funds
     pub bump: u8,
     pub fee_collector_bump: u8,
 }
@@ -11,13 +53,145 @@ pub struct Config {
 impl Config {
     pub const LEN: usize = 8  // discriminator
         + 32  // admin
+        + 33  // pending_admin
+        + 32  // guardian
+        + 1   // escrows_paused
+        + 1   // emergency_mode
         + 2   // fee_basis_points
+        + 2   // arbiter_fee_basis_points
+        + 8   // min_arbiter_fee
+        + 2   // buyer_dispute_fee_basis_points
+        + 2   // seller_dispute_fee_basis_points
+        + 2   // split_dispute_fee_basis_points
+        + 4 + FeeTier::LEN * MAX_FEE_TIERS  // fee_tiers
+        + 8   // panel_threshold
+        + 1   // panel_size
+        + 4   // voting_period
+        + 8   // appeal_bond
+        + 4   // appeal_window
+        + 8   // min_arbiter_stake
+        + 4   // stake_cooldown
+        + 8   // overturn_slash
+        + 4   // dispute_window
+        + 4   // escrow_timeout
         + 1   // bump
         + 1;  // fee_collector_bump
 
+    /// Config with every setting not given at its default
+    pub fn new(
+        admin: Pubkey,
+        fee_basis_points: u16,
+        arbiter_fee_basis_points: u16,
+        min_arbiter_fee: u64,
+        bump: u8,
+        fee_collector_bump: u8,
+    ) -> Self {
+        Self {
+            admin,
+            pending_admin: None,
+            guardian: admin,
+            escrows_paused: false,
+            emergency_mode: false,
+            fee_basis_points,
+            arbiter_fee_basis_points,
+            min_arbiter_fee,
+            // Disputes pay the regular platform fee until the admin sets other rates
+            buyer_dispute_fee_basis_points: fee_basis_points,
+            seller_dispute_fee_basis_points: fee_basis_points,
+            split_dispute_fee_basis_points: fee_basis_points,
+            fee_tiers: Vec::new(),
+            // Panels stay disabled until the admin sets a threshold
+            panel_threshold: 0,
+            panel_size: MIN_PANEL_SIZE as u8,
+            voting_period: DEFAULT_VOTING_PERIOD,
+            appeal_bond: DEFAULT_APPEAL_BOND,
+            appeal_window: DEFAULT_APPEAL_WINDOW,
+            min_arbiter_stake: DEFAULT_MIN_ARBITER_STAKE,
+            stake_cooldown: DEFAULT_STAKE_COOLDOWN,
+            overturn_slash: DEFAULT_OVERTURN_SLASH,
+            dispute_window: DEFAULT_DISPUTE_WINDOW,
+            escrow_timeout: DEFAULT_ESCROW_TIMEOUT,
+            bump,
+            fee_collector_bump,
+        }
+    }
+
     pub fn is_admin(&self, key: &Pubkey) -> bool {
         self.admin == *key
     }
+
+    pub fn can_pause(&self, key: &Pubkey) -> bool {
+        self.admin == *key || self.guardian == *key
+    }
+
+    /// Whether a dispute over `amount` lamports must be decided by a panel
+    pub fn requires_panel(&self, amount: u64, is_token_escrow: bool) -> bool {
+        // The threshold is in lamports, token escrows always use a single arbiter
+        self.panel_threshold > 0 && !is_token_escrow && amount >= self.panel_threshold
+    }
+
+    /// Panel size for a dispute on `escrow`, zero when a single arbiter decides
+    pub fn panel_size_for(&self, escrow: &Escrow) -> u8 {
+        if !self.requires_panel(escrow.remaining_amount(), escrow.is_token_escrow()) {
+            return 0;
+        }
+        // A panel is drawn from the arbiters the parties agreed on and always has an odd size.
+        // If the config changed since creation and too few were agreed, one arbiter decides.
+        let size = self.panel_size.min(escrow.arbiters.len() as u8);
+        let size = if size % 2 == 0 { size.saturating_sub(1) } else { size };
+        if size as usize >= MIN_PANEL_SIZE {
+            size
+        } else {
+            0
+        }
+    }
+
+    /// Platform fee owed on `amount` (basis points: 100 = 1%)
+    pub fn calculate_fee(&self, amount: u64) -> Option<u64> {
+        amount
+            .checked_mul(self.fee_basis_points as u64)?
+            .checked_div(10_000)
+    }
+
+    /// Lowest fee rate among the tiers the seller qualifies for, never above the base fee
+    pub fn seller_fee_basis_points(&self, seller_reputation: &Reputation) -> u16 {
+        self.fee_tiers
+            .iter()
+            .filter(|tier| tier.qualifies(seller_reputation))
+            .map(|tier| tier.fee_basis_points)
+            .fold(self.fee_basis_points, u16::min)
+    }
+
+    /// Platform fee owed on `amount` after the seller's tier discount
+    pub fn calculate_seller_fee(&self, amount: u64, seller_reputation: &Reputation) -> Option<u64> {
+        amount
+            .checked_mul(self.seller_fee_basis_points(seller_reputation) as u64)?
+            .checked_div(10_000)
+    }
+
+    /// Arbiter fee owed on a disputed `amount`, never more than `amount` itself
+    pub fn calculate_arbiter_fee(&self, amount: u64, is_token_escrow: bool) -> Option<u64> {
+        let fee = amount
+            .checked_mul(self.arbiter_fee_basis_points as u64)?
+            .checked_div(10_000)?;
+        // The flat minimum is denominated in lamports, so it only applies to SOL escrows
+        let fee = if is_token_escrow { fee } else { fee.max(self.min_arbiter_fee) };
+        Some(fee.min(amount))
+    }
+
+    /// Platform fee owed on a disputed `amount`, at the rate for its outcome
+    pub fn calculate_dispute_fee(&self, amount: u64, resolution: &DisputeResolution) -> Option<u64> {
+        let basis_points = match resolution {
+            DisputeResolution::FavorBuyer => self.buyer_dispute_fee_basis_points,
+            DisputeResolution::FavorSeller => self.seller_dispute_fee_basis_points,
+            DisputeResolution::Split | DisputeResolution::PartialSplit { .. } => {
+                self.split_dispute_fee_basis_points
+            }
+        };
+        amount
+            .checked_mul(basis_points as u64)?
+            .checked_div(10_000)
+    }
 }
 
 #[account]
@@ -26,7 +200,18 @@ pub struct Arbiter {
     pub added_by: Pubkey,
     pub added_at: i64,
     pub is_active: bool,
+    pub stake: u64,  // Lamports held in the stake vault
+    pub unstake_requested_at: i64,  // Zero unless a withdrawal is pending
+    pub disputes_resolved: u64,
+    pub favor_buyer_rulings: u64,
+    pub favor_seller_rulings: u64,
+    pub split_rulings: u64,  // 50/50 and partial splits
+    pub total_resolution_time: u64,  // Seconds from dispute to ruling, summed over disputes_resolved
+    pub appeals_lost: u64,  // Rulings overturned on appeal
+    pub last_active_at: i64,
+    pub pending_rulings: u32,  // Rulings not yet executed, the account can't be closed until zero
     pub bump: u8,
+    pub stake_vault_bump: u8,
 }
 
 impl Arbiter {
@@ -35,9 +220,119 @@ impl Arbiter {
         + 32  // added_by
         + 8   // added_at
         + 1   // is_active
-        + 1;  // bump
+        + 8   // stake
+        + 8   // unstake_requested_at
+        + 8   // disputes_resolved
+        + 8   // favor_buyer_rulings
+        + 8   // favor_seller_rulings
+        + 8   // split_rulings
+        + 8   // total_resolution_time
+        + 8   // appeals_lost
+        + 8   // last_active_at
+        + 4   // pending_rulings
+        + 1   // bump
+        + 1;  // stake_vault_bump
+
+    /// Active arbiter with no stake and no rulings yet
+    pub fn new(
+        arbiter: Pubkey,
+        added_by: Pubkey,
+        added_at: i64,
+        bump: u8,
+        stake_vault_bump: u8,
+    ) -> Self {
+        Self {
+            arbiter,
+            added_by,
+            added_at,
+            is_active: true,
+            stake: 0,
+            unstake_requested_at: 0,
+            disputes_resolved: 0,
+            favor_buyer_rulings: 0,
+            favor_seller_rulings: 0,
+            split_rulings: 0,
+            total_resolution_time: 0,
+            appeals_lost: 0,
+            last_active_at: 0,
+            pending_rulings: 0,
+            bump,
+            stake_vault_bump,
+        }
+    }
 
-    pub fn can_resolve_disputes(&self) -> bool {
-        self.is_active
+    /// Active, holding at least `min_stake` and not on the way out
+    pub fn can_resolve_disputes(&self, min_stake: u64) -> bool {
+        self.is_active && self.stake >= min_stake && self.unstake_requested_at == 0
     }
+
+    /// Records a ruling this arbiter made and has yet to see executed
+    pub fn record_ruling(&mut self, resolution: &DisputeResolution, resolution_time: i64, now: i64) {
+        self.record_decision(resolution, resolution_time, now);
+        self.add_pending_ruling();
+    }
+
+    /// Records a decision made `resolution_time` seconds after the dispute was raised, alone
+    /// or as a panel member
+    pub fn record_decision(&mut self, resolution: &DisputeResolution, resolution_time: i64, now: i64) {
+        self.disputes_resolved = self.disputes_resolved.saturating_add(1);
+        let count = match resolution {
+            DisputeResolution::FavorBuyer => &mut self.favor_buyer_rulings,
+            DisputeResolution::FavorSeller => &mut self.favor_seller_rulings,
+            DisputeResolution::Split | DisputeResolution::PartialSplit { .. } => {
+                &mut self.split_rulings
+            }
+        };
+        *count = count.saturating_add(1);
+        self.total_resolution_time = self
+            .total_resolution_time
+            .saturating_add(resolution_time.max(0) as u64);
+        self.last_active_at = now;
+    }
+
+    /// A ruling was submitted by this arbiter, it stays pending until executed
+    pub fn add_pending_ruling(&mut self) {
+        self.pending_rulings = self.pending_rulings.saturating_add(1);
+    }
+
+    /// A ruling of this arbiter was executed
+    pub fn complete_ruling(&mut self) {
+        self.pending_rulings = self.pending_rulings.saturating_sub(1);
+    }
+
+    pub fn can_close(&self) -> bool {
+        !self.is_active && self.stake == 0 && self.pending_rulings == 0
+    }
+
+    pub fn record_appeal_lost(&mut self) {
+        self.appeals_lost = self.appeals_lost.saturating_add(1);
+    }
+
+    /// Average seconds from dispute to ruling
+    pub fn average_resolution_time(&self) -> u64 {
+        self.total_resolution_time
+            .checked_div(self.disputes_resolved)
+            .unwrap_or(0)
+    }
+
+    pub fn can_withdraw_stake(&self, now: i64, cooldown: u32) -> bool {
+        self.unstake_requested_at > 0
+            && now >= self.unstake_requested_at.saturating_add(cooldown as i64)
+    }
+}
+
+#[account]
+pub struct ReputationAuthority {
+    pub authority: Pubkey,  // Signer allowed to adjust reputation, e.g. another program's PDA
+    pub added_by: Pubkey,
+    pub added_at: i64,
+    pub bump: u8,
+}
+
+impl ReputationAuthority {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // authority
+        + 32  // added_by
+        + 8   // added_at
+        + 1;  // bump
 }
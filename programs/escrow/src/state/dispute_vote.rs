@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use super::DisputeResolution;
+use crate::constants::MAX_PANEL_SIZE;
+
+#[account]
+pub struct DisputeVote {
+    pub escrow: Pubkey,
+    pub votes: Vec<PanelVote>,  // In the order they were cast
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PanelVote {
+    pub arbiter: Pubkey,
+    pub resolution: DisputeResolution,
+}
+
+impl PanelVote {
+    pub const LEN: usize = 32  // arbiter
+        + 3;  // resolution
+}
+
+impl DisputeVote {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // escrow
+        + 4 + PanelVote::LEN * MAX_PANEL_SIZE  // votes
+        + 1;  // bump
+
+    pub fn has_voted(&self, arbiter: &Pubkey) -> bool {
+        self.votes.iter().any(|vote| vote.arbiter == *arbiter)
+    }
+
+    pub fn count(&self, resolution: &DisputeResolution) -> u8 {
+        self.votes
+            .iter()
+            .filter(|vote| vote.resolution == *resolution)
+            .count() as u8
+    }
+
+    /// Decided resolution and the votes behind it. A majority of the panel decides at once,
+    /// after the deadline the plurality of cast votes decides and ties fall back to a 50/50 split.
+    pub fn tally(&self, panel_size: u8, deadline_passed: bool) -> Option<(DisputeResolution, u8)> {
+        let most_votes = self.votes.iter().map(|vote| self.count(&vote.resolution)).max()?;
+        let mut leaders = self
+            .votes
+            .iter()
+            .map(|vote| vote.resolution)
+            .filter(|resolution| self.count(resolution) == most_votes);
+        let leader = leaders.next()?;
+
+        if most_votes > panel_size / 2 {
+            return Some((leader, most_votes));
+        }
+        if !deadline_passed {
+            return None;
+        }
+        if leaders.all(|resolution| resolution == leader) {
+            Some((leader, most_votes))
+        } else {
+            Some((DisputeResolution::Split, most_votes))
+        }
+    }
+}
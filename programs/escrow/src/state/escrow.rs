@@ -1,12 +1,33 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{MAX_ESCROW_ARBITERS, MAX_MILESTONES};
+
 #[account]
 pub struct Escrow {
     pub buyer: Pubkey,
     pub seller: Pubkey,
+    pub escrow_id: u64,  // Client-chosen nonce, lets one buyer/seller pair hold many escrows
     pub amount: u64,
+    pub mint: Option<Pubkey>,  // None for native SOL escrows
+    pub milestones: Vec<u64>,  // Ordered milestone amounts, summing to `amount`
+    pub milestones_released: u8,
+    pub released_amount: u64,
+    pub arbiters: Vec<Pubkey>,  // Arbiters agreed at creation, empty allows any active arbiter
+    pub dispute_reason: Option<DisputeReason>,
+    pub disputed_by: Option<Pubkey>,
+    pub disputed_at: i64,
+    pub panel_size: u8,  // Votes needed to decide the dispute, zero for a single arbiter
+    pub voting_deadline: i64,  // After this the panel's plurality decides
+    pub pending_resolution: Option<DisputeResolution>,  // Ruling awaiting execution
+    pub ruled_by: Option<Pubkey>,  // Arbiter who made the pending ruling
+    pub ruling_at: i64,
+    pub appeal_deadline: i64,  // Ruling can be appealed until this, zero when it can't
+    pub appeal: Option<Appeal>,
+    pub evidence_count: u16,
     pub status: EscrowStatus,
     pub created_at: i64,
+    pub accepted_at: i64,  // Set when the seller accepts, zero until then
+    pub keep_open: bool,  // Stays open for audit after settlement until `close_escrow`
     pub bump: u8,
 }
 
@@ -17,15 +38,110 @@ pub enum EscrowStatus {
     Completed,
     Cancelled,
     Disputed,
+    Ruled,  // Dispute decided, funds held until the appeal window closes
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeReason {
+    NotDelivered,
+    NotAsDescribed,
+    LateDelivery,
+    PaymentIssue,
+    Fraud,
+    Other,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeResolution {
+    FavorBuyer,   // Refund the buyer
+    FavorSeller,  // Pay the seller
+    Split,        // Split funds 50/50
+    PartialSplit { buyer_basis_points: u16 },  // Buyer's share in basis points, seller gets the rest
+}
+
+/// An appealed ruling, kept until the appeal is decided and the bond settled
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct Appeal {
+    pub appellant: Pubkey,
+    pub bond: u64,  // Lamports held in the escrow account
+    pub original_resolution: DisputeResolution,
+    pub original_arbiter: Pubkey,
+    pub appealed_at: i64,
+}
+
+impl Appeal {
+    pub const LEN: usize = 32  // appellant
+        + 8   // bond
+        + 3   // original_resolution
+        + 32  // original_arbiter
+        + 8;  // appealed_at
+
+    /// Whether `resolution` gives the appellant a larger share than the appealed ruling
+    pub fn is_overturned_by(&self, resolution: &DisputeResolution, buyer: &Pubkey) -> bool {
+        let (Some(original), Some(decided)) = (
+            self.original_resolution.buyer_basis_points(),
+            resolution.buyer_basis_points(),
+        ) else {
+            return false;
+        };
+        if self.appellant == *buyer {
+            decided > original
+        } else {
+            decided < original
+        }
+    }
+}
+
+impl DisputeResolution {
+    /// Buyer's share of the disputed funds in basis points
+    pub fn buyer_basis_points(&self) -> Option<u16> {
+        match self {
+            DisputeResolution::FavorBuyer => Some(10_000),
+            DisputeResolution::FavorSeller => Some(0),
+            DisputeResolution::Split => Some(5_000),
+            DisputeResolution::PartialSplit { buyer_basis_points } => {
+                (*buyer_basis_points <= 10_000).then_some(*buyer_basis_points)
+            }
+        }
+    }
+
+    /// Splits `amount` into (buyer, seller) shares that always add up to `amount`
+    pub fn split_amount(&self, amount: u64) -> Option<(u64, u64)> {
+        let buyer_basis_points = self.buyer_basis_points()?;
+        let buyer_amount = (amount as u128)
+            .checked_mul(buyer_basis_points as u128)?
+            .checked_div(10_000)? as u64;
+        let seller_amount = amount.checked_sub(buyer_amount)?;
+        Some((buyer_amount, seller_amount))
+    }
 }
 
 impl Escrow {
     pub const LEN: usize = 8  // discriminator
         + 32  // buyer
         + 32  // seller
+        + 8   // escrow_id
         + 8   // amount
+        + 33  // mint
+        + 4 + 8 * MAX_MILESTONES  // milestones
+        + 1   // milestones_released
+        + 8   // released_amount
+        + 4 + 32 * MAX_ESCROW_ARBITERS  // arbiters
+        + 2   // dispute_reason
+        + 33  // disputed_by
+        + 8   // disputed_at
+        + 1   // panel_size
+        + 8   // voting_deadline
+        + 4   // pending_resolution
+        + 33  // ruled_by
+        + 8   // ruling_at
+        + 8   // appeal_deadline
+        + 1 + Appeal::LEN  // appeal
+        + 2   // evidence_count
         + 1   // enum
         + 8   // created_at
+        + 8   // accepted_at
+        + 1   // keep_open
         + 1;  // bump
 
     pub fn is_active(&self) -> bool {
@@ -36,8 +152,87 @@ impl Escrow {
         matches!(self.status, EscrowStatus::Active)
     }
 
+    pub fn can_accept(&self) -> bool {
+        matches!(self.status, EscrowStatus::Initialized)
+    }
+
     pub fn can_cancel(&self) -> bool {
-        matches!(self.status, EscrowStatus::Initialized | EscrowStatus::Active)
+        matches!(self.status, EscrowStatus::Initialized)
+    }
+
+    pub fn is_dispute_window_open(&self, now: i64, dispute_window: u32) -> bool {
+        now <= self.accepted_at.saturating_add(dispute_window as i64)
+    }
+
+    pub fn has_timed_out(&self, now: i64, escrow_timeout: u32) -> bool {
+        now >= self.accepted_at.saturating_add(escrow_timeout as i64)
+    }
+
+    /// Index and amount of the next milestone awaiting release
+    pub fn next_milestone(&self) -> Option<(u8, u64)> {
+        let index = self.milestones_released;
+        self.milestones.get(index as usize).map(|amount| (index, *amount))
+    }
+
+    pub fn all_milestones_released(&self) -> bool {
+        self.milestones_released as usize >= self.milestones.len()
+    }
+
+    /// Funds still held for unreleased milestones
+    pub fn remaining_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.released_amount)
+    }
+
+    pub fn is_assigned_arbiter(&self, arbiter: &Pubkey) -> bool {
+        self.arbiters.is_empty() || self.arbiters.contains(arbiter)
+    }
+
+    pub fn is_token_escrow(&self) -> bool {
+        self.mint.is_some()
+    }
+
+    /// `amount` as reputation volume, token amounts aren't lamports so they don't count
+    pub fn lamport_volume(&self, amount: u64) -> u64 {
+        if self.is_token_escrow() {
+            0
+        } else {
+            amount
+        }
+    }
+
+    pub fn is_panel_dispute(&self) -> bool {
+        self.panel_size > 0
+    }
+
+    /// Whether the pending ruling can still be appealed at `now`
+    pub fn can_appeal(&self, now: i64) -> bool {
+        self.status == EscrowStatus::Ruled && self.appeal.is_none() && now < self.appeal_deadline
+    }
+
+    /// Whether the pending ruling is final: its appeal was decided or the window has closed
+    pub fn can_execute_ruling(&self, now: i64) -> bool {
+        self.status == EscrowStatus::Ruled && (self.appeal.is_some() || now >= self.appeal_deadline)
+    }
+
+    /// Whether the pending ruling leaves `party` at most half of the disputed funds
+    pub fn is_losing_party(&self, party: &Pubkey) -> bool {
+        let Some(buyer_basis_points) = self
+            .pending_resolution
+            .and_then(|resolution| resolution.buyer_basis_points())
+        else {
+            return false;
+        };
+        (*party == self.buyer && buyer_basis_points <= 5_000)
+            || (*party == self.seller && buyer_basis_points >= 5_000)
+    }
+
+    /// Whether an assigned arbiter outside `excluded` is left to hear an appeal
+    pub fn has_appeal_arbiter(&self, excluded: &[Pubkey]) -> bool {
+        self.arbiters.is_empty() || self.arbiters.iter().any(|arbiter| !excluded.contains(arbiter))
+    }
+
+    pub fn was_disputed_by(&self, key: &Pubkey) -> bool {
+        self.disputed_by == Some(*key)
     }
 
     pub fn is_finalized(&self) -> bool {
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_EVIDENCE_URI_LEN;
+
+#[account]
+pub struct Evidence {
+    pub escrow: Pubkey,
+    pub submitted_by: Pubkey,
+    pub index: u16,
+    pub content_hash: [u8; 32],  // e.g. SHA-256 of the off-chain file
+    pub uri: String,
+    pub category: EvidenceCategory,
+    pub submitted_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum EvidenceCategory {
+    Communication,
+    Delivery,
+    Payment,
+    Contract,
+    Other,
+}
+
+impl Evidence {
+    pub const LEN: usize = 8  // discriminator
+        + 32  // escrow
+        + 32  // submitted_by
+        + 2   // index
+        + 32  // content_hash
+        + 4 + MAX_EVIDENCE_URI_LEN  // uri
+        + 1   // enum
+        + 8   // submitted_at
+        + 1;  // bump
+}
@@ -1,7 +1,11 @@
 pub mod escrow;
 pub mod reputation;
 pub mod config;
+pub mod evidence;
+pub mod dispute_vote;
 
 pub use escrow::*;
 pub use reputation::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use evidence::*;
+pub use dispute_vote::*;
\ No newline at end of file
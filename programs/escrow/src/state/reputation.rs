@@ -1,17 +1,42 @@
 use anchor_lang::prelude::*;
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationUpdate {
+    Successful,
+    Failed,
+}
+
 #[account]
 pub struct Reputation {
     pub user: Pubkey,
     pub successful_trades: u64,
     pub failed_trades: u64,
+    pub total_volume: u64,  // Lamports settled across successful SOL trades
+    pub disputed_volume: u64,  // Lamports that went through a dispute
+    pub disputes_raised: u64,
+    pub disputes_lost: u64,
+    pub last_activity_at: i64,
 }
 
 impl Reputation {
     pub const LEN: usize = 8  // discriminator
         + 32  // user
         + 8   // successful_trades
-        + 8;  // failed_trades
+        + 8   // failed_trades
+        + 8   // total_volume
+        + 8   // disputed_volume
+        + 8   // disputes_raised
+        + 8   // disputes_lost
+        + 8;  // last_activity_at
+
+    /// Claims a freshly created account for `user`, returns false if it was already in use
+    pub fn initialize_if_new(&mut self, user: Pubkey) -> bool {
+        if self.user != Pubkey::default() {
+            return false;
+        }
+        self.user = user;
+        true
+    }
 
     pub fn total_trades(&self) -> u64 {
         self.successful_trades.saturating_add(self.failed_trades)
@@ -25,6 +50,15 @@ impl Reputation {
         (self.successful_trades as f64 / total as f64) * 100.0
     }
 
+    /// Success rate in basis points (10000 = 100%), rounded down
+    pub fn success_rate_bps(&self) -> u16 {
+        let total = self.total_trades();
+        if total == 0 {
+            return 0;
+        }
+        (self.successful_trades as u128 * 10_000 / total as u128) as u16
+    }
+
     pub fn increment_successful(&mut self) {
         self.successful_trades = self.successful_trades.saturating_add(1);
     }
@@ -32,4 +66,18 @@ impl Reputation {
     pub fn increment_failed(&mut self) {
         self.failed_trades = self.failed_trades.saturating_add(1);
     }
+
+    pub fn record_volume(&mut self, volume: u64) {
+        self.total_volume = self.total_volume.saturating_add(volume);
+    }
+
+    pub fn record_dispute(&mut self, volume: u64, raised: bool, lost: bool) {
+        self.disputed_volume = self.disputed_volume.saturating_add(volume);
+        if raised {
+            self.disputes_raised = self.disputes_raised.saturating_add(1);
+        }
+        if lost {
+            self.disputes_lost = self.disputes_lost.saturating_add(1);
+        }
+    }
 }
\ No newline at end of file
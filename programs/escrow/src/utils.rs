@@ -0,0 +1,317 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+};
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{
+    constants::{ESCROW_SEED, STAKE_VAULT_SEED},
+    errors::EscrowError,
+    events::{ArbiterSlashed, ReputationUpdated},
+    state::{Arbiter, Escrow, PanelVote, Reputation},
+};
+
+/// Token accounts backing an SPL Token or Token-2022 escrow
+pub struct TokenVault<'a, 'info> {
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub vault: &'a InterfaceAccount<'info, TokenAccount>,
+    pub token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'a, 'info> TokenVault<'a, 'info> {
+    /// Returns the vault only when every token account was supplied
+    pub fn from_accounts(
+        mint: &'a Option<InterfaceAccount<'info, Mint>>,
+        vault: &'a Option<InterfaceAccount<'info, TokenAccount>>,
+        token_program: &'a Option<Interface<'info, TokenInterface>>,
+    ) -> Option<Self> {
+        Some(Self {
+            mint: mint.as_ref()?,
+            vault: vault.as_ref()?,
+            token_program: token_program.as_ref()?,
+        })
+    }
+}
+
+/// Token-2022 mint extensions that leave transfers in and out of the vault untouched. Anything
+/// else can take a fee, block or claw back the transfer, so the vault could hold less than the
+/// escrow amount.
+const SUPPORTED_MINT_EXTENSIONS: &[ExtensionType] = &[
+    ExtensionType::MintCloseAuthority,
+    ExtensionType::InterestBearingConfig,
+    ExtensionType::ScaledUiAmount,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+];
+
+/// Returns whether an escrow can hold the mint. SPL Token mints always can, Token-2022 mints
+/// only with supported extensions.
+pub fn is_supported_mint(mint: &AccountInfo) -> bool {
+    if *mint.owner != spl_token_2022::ID {
+        return true;
+    }
+    let Ok(data) = mint.try_borrow_data() else {
+        return false;
+    };
+    StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)
+        .and_then(|state| state.get_extension_types())
+        .is_ok_and(|extensions| {
+            extensions
+                .iter()
+                .all(|extension| SUPPORTED_MINT_EXTENSIONS.contains(extension))
+        })
+}
+
+/// Runs `f` with the signer seeds of the escrow PDA
+pub fn with_escrow_signer<T>(escrow: &Escrow, f: impl FnOnce(&[&[&[u8]]]) -> T) -> T {
+    let escrow_id = escrow.escrow_id.to_le_bytes();
+    let bump = [escrow.bump];
+    let seeds: &[&[u8]] = &[
+        ESCROW_SEED,
+        escrow.buyer.as_ref(),
+        escrow.seller.as_ref(),
+        &escrow_id,
+        &bump,
+    ];
+    f(&[seeds])
+}
+
+/// Moves lamports out of a program-owned account
+pub fn transfer_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(EscrowError::InsufficientFunds)?;
+
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(EscrowError::Overflow)?;
+
+    Ok(())
+}
+
+/// Pays `amount` out of the escrow, as lamports for SOL escrows or through
+/// `transfer_checked` from the vault for token escrows
+pub fn pay_from_escrow<'info>(
+    escrow: &Account<'info, Escrow>,
+    token_vault: Option<&TokenVault<'_, 'info>>,
+    recipient: &AccountInfo<'info>,
+    recipient_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let Some(mint) = escrow.mint else {
+        return transfer_lamports(&escrow.to_account_info(), recipient, amount);
+    };
+
+    let token_vault = token_vault.ok_or(EscrowError::MissingTokenAccounts)?;
+    let recipient_token_account =
+        recipient_token_account.ok_or(EscrowError::MissingTokenAccounts)?;
+    require_keys_eq!(token_vault.mint.key(), mint, EscrowError::InvalidMint);
+
+    with_escrow_signer(escrow, |signer_seeds| {
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_vault.token_program.to_account_info(),
+                TransferChecked {
+                    from: token_vault.vault.to_account_info(),
+                    mint: token_vault.mint.to_account_info(),
+                    to: recipient_token_account.to_account_info(),
+                    authority: escrow.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            token_vault.mint.decimals,
+        )
+    })
+}
+
+/// Closes a settled escrow, returning the vault and escrow rent to the buyer. Escrows kept
+/// open for audit only close their empty vault, `close_escrow` closes the escrow later.
+pub fn close_escrow<'info>(
+    escrow: &Account<'info, Escrow>,
+    token_vault: Option<&TokenVault<'_, 'info>>,
+    buyer: &AccountInfo<'info>,
+) -> Result<()> {
+    if escrow.is_token_escrow() {
+        let token_vault = token_vault.ok_or(EscrowError::MissingTokenAccounts)?;
+
+        with_escrow_signer(escrow, |signer_seeds| {
+            token_interface::close_account(CpiContext::new_with_signer(
+                token_vault.token_program.to_account_info(),
+                CloseAccount {
+                    account: token_vault.vault.to_account_info(),
+                    destination: buyer.clone(),
+                    authority: escrow.to_account_info(),
+                },
+                signer_seeds,
+            ))
+        })?;
+    }
+
+    if escrow.keep_open {
+        return Ok(());
+    }
+    escrow.close(buyer.clone())
+}
+
+/// Dispute side of a settlement, folded into a party's reputation
+pub struct DisputeRecord {
+    pub volume: u64,
+    pub raised: bool,
+    pub lost: bool,
+}
+
+/// What a settled trade means for one party
+pub struct TradeOutcome {
+    pub successful: bool,
+    pub volume: u64,  // Added to total_volume only when successful
+    pub dispute: Option<DisputeRecord>,
+}
+
+/// Records a trade outcome on a party's reputation account
+pub fn record_trade_outcome(
+    reputation: &mut Account<'_, Reputation>,
+    outcome: TradeOutcome,
+    now: i64,
+    party: &str,
+) {
+    // Only successful trades add to the volume, any settlement counts as activity
+    if outcome.successful {
+        reputation.increment_successful();
+        reputation.record_volume(outcome.volume);
+    } else {
+        reputation.increment_failed();
+    }
+    reputation.last_activity_at = now;
+    if let Some(dispute) = outcome.dispute {
+        reputation.record_dispute(dispute.volume, dispute.raised, dispute.lost);
+    }
+
+    emit!(ReputationUpdated::new(reputation));
+    msg!(
+        "{} reputation updated: {} successful, {} failed trades, {} volume",
+        party,
+        reputation.successful_trades,
+        reputation.failed_trades,
+        reputation.total_volume
+    );
+}
+
+/// Runs `update` on the arbiter account of every panel vote. `panel_arbiters` are their
+/// arbiter accounts in the order they voted. An arbiter the instruction already holds as
+/// `loaded` is updated there, its entry in `panel_arbiters` is only checked.
+pub fn update_panel_arbiters<'info>(
+    votes: &[PanelVote],
+    panel_arbiters: &'info [AccountInfo<'info>],
+    mut loaded: Option<&mut Account<'info, Arbiter>>,
+    mut update: impl FnMut(&PanelVote, &mut Arbiter),
+) -> Result<()> {
+    require!(
+        panel_arbiters.len() == votes.len(),
+        EscrowError::InvalidPanelAccounts
+    );
+    for (vote, account) in votes.iter().zip(panel_arbiters) {
+        match loaded.as_deref_mut() {
+            Some(arbiter_account) if arbiter_account.arbiter == vote.arbiter => {
+                require_keys_eq!(
+                    account.key(),
+                    arbiter_account.key(),
+                    EscrowError::InvalidPanelAccounts
+                );
+                update(vote, arbiter_account);
+            }
+            _ => {
+                // Only this program creates arbiter accounts, one per arbiter
+                let mut arbiter_account = Account::<Arbiter>::try_from(account)?;
+                require_keys_eq!(
+                    arbiter_account.arbiter,
+                    vote.arbiter,
+                    EscrowError::InvalidPanelAccounts
+                );
+                update(vote, &mut arbiter_account);
+                arbiter_account.exit(&crate::ID)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Moves up to `amount` of an arbiter's stake from their vault to `recipient`, returns the
+/// amount actually slashed
+pub fn slash_stake<'info>(
+    arbiter_account: &mut Account<'info, Arbiter>,
+    stake_vault: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+    authority: Pubkey,
+    reason: String,
+) -> Result<u64> {
+    let arbiter_key = arbiter_account.arbiter;
+    let vault_seeds = &[
+        STAKE_VAULT_SEED,
+        arbiter_key.as_ref(),
+        &[arbiter_account.stake_vault_bump],
+    ];
+    require_keys_eq!(
+        Pubkey::create_program_address(vault_seeds, &crate::ID)
+            .map_err(|_| EscrowError::InvalidStakeVault)?,
+        stake_vault.key(),
+        EscrowError::InvalidStakeVault
+    );
+
+    // The vault has to end up empty or rent-exempt, so a partial slash stops at the rent reserve
+    let vault_balance = stake_vault.lamports();
+    let rent_reserve = Rent::get()?.minimum_balance(0);
+    let mut slashed = amount.min(arbiter_account.stake);
+    let remaining = vault_balance.saturating_sub(slashed);
+    if remaining > 0 && remaining < rent_reserve {
+        slashed = slashed.min(vault_balance.saturating_sub(rent_reserve));
+    }
+    if slashed > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                Transfer {
+                    from: stake_vault.clone(),
+                    to: recipient.clone(),
+                },
+                &[&vault_seeds[..]],
+            ),
+            slashed,
+        )?;
+        arbiter_account.stake -= slashed;
+    }
+
+    emit!(ArbiterSlashed {
+        arbiter: arbiter_key,
+        authority,
+        recipient: recipient.key(),
+        amount: slashed,
+        remaining_stake: arbiter_account.stake,
+        reason,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    msg!(
+        "Arbiter {} slashed {} lamports, {} remaining",
+        arbiter_key,
+        slashed,
+        arbiter_account.stake
+    );
+
+    Ok(slashed)
+}
@@ -3,6 +3,7 @@ import { Program } from "@coral-xyz/anchor";
 import { Escrow } from "../target/types/escrow";
 import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { assert } from "chai";
+import { createHash } from "crypto";
 
 describe("Full Integration Test", () => {
   const provider = anchor.AnchorProvider.env();
@@ -15,6 +16,11 @@ describe("Full Integration Test", () => {
   const ARBITER_SEED = Buffer.from("arbiter");
   const ESCROW_SEED = Buffer.from("escrow");
   const REPUTATION_SEED = Buffer.from("reputation");
+  const EVIDENCE_SEED = Buffer.from("evidence");
+  const VAULT_SEED = Buffer.from("vault");
+
+  const TOKEN_PROGRAM_ID = new PublicKey("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+  const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 
   let admin: anchor.web3.Keypair;
   let arbiter: anchor.web3.Keypair;
@@ -28,14 +34,156 @@ describe("Full Integration Test", () => {
   let arbiterPda: PublicKey;
   let escrowPda: PublicKey;
   let escrow2Pda: PublicKey;
+  let concurrentEscrowPda: PublicKey;
   let buyerReputationPda: PublicKey;
   let sellerReputationPda: PublicKey;
   let buyer2ReputationPda: PublicKey;
   let seller2ReputationPda: PublicKey;
 
   const FEE_BASIS_POINTS = 250; // 2.5%
+  const ARBITER_FEE_BASIS_POINTS = 100; // 1%
+  const MIN_ARBITER_FEE = 0.01 * LAMPORTS_PER_SOL;
+  const MIN_ARBITER_STAKE = LAMPORTS_PER_SOL;
   const ESCROW_AMOUNT = 5 * LAMPORTS_PER_SOL;
   const ESCROW_AMOUNT_2 = 3 * LAMPORTS_PER_SOL;
+  const ESCROW_ID = new anchor.BN(1);
+  const ESCROW_ID_2 = new anchor.BN(1);
+
+  const escrowIdSeed = (id: anchor.BN) => id.toArrayLike(Buffer, "le", 8);
+  const evidencePdaFor = (escrow: PublicKey, createdAt: anchor.BN, index: number) =>
+    PublicKey.findProgramAddressSync(
+      [
+        EVIDENCE_SEED,
+        escrow.toBuffer(),
+        createdAt.toArrayLike(Buffer, "le", 8),
+        new anchor.BN(index).toArrayLike(Buffer, "le", 2),
+      ],
+      program.programId
+    )[0];
+
+  // Token instructions are built by hand, both token programs share these layouts
+  const MINT_SIZE = 82;
+  const TOKEN_ACCOUNT_SIZE = 165;
+  // Base state padded to the account size, the account type byte and one TLV entry
+  const extendedSize = (extensionLen: number) => TOKEN_ACCOUNT_SIZE + 1 + 4 + extensionLen;
+  const TRANSFER_FEE_CONFIG_LEN = 108;
+  const TRANSFER_FEE_AMOUNT_LEN = 8;
+
+  const createTokenProgramAccount = async (
+    payer: anchor.web3.Keypair,
+    space: number,
+    tokenProgram: PublicKey,
+    initInstructions: (account: PublicKey) => anchor.web3.TransactionInstruction[]
+  ) => {
+    const account = anchor.web3.Keypair.generate();
+    const tx = new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.createAccount({
+        fromPubkey: payer.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: await provider.connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: tokenProgram,
+      }),
+      ...initInstructions(account.publicKey)
+    );
+    await provider.sendAndConfirm(tx, [payer, account]);
+    return account.publicKey;
+  };
+
+  const createMint = (
+    authority: anchor.web3.Keypair,
+    tokenProgram: PublicKey,
+    transferFeeBasisPoints?: number
+  ) => {
+    const withFee = transferFeeBasisPoints !== undefined;
+    return createTokenProgramAccount(
+      authority,
+      withFee ? extendedSize(TRANSFER_FEE_CONFIG_LEN) : MINT_SIZE,
+      tokenProgram,
+      (mint) => {
+        const instructions: anchor.web3.TransactionInstruction[] = [];
+        if (withFee) {
+          // TransferFeeExtension::InitializeTransferFeeConfig, no authorities, no fee cap
+          const basisPoints = Buffer.alloc(2);
+          basisPoints.writeUInt16LE(transferFeeBasisPoints, 0);
+          const data = Buffer.concat([Buffer.from([26, 0, 0, 0]), basisPoints, Buffer.alloc(8, 0xff)]);
+          instructions.push(
+            new anchor.web3.TransactionInstruction({
+              programId: tokenProgram,
+              keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+              data,
+            })
+          );
+        }
+        // InitializeMint2 with 6 decimals and no freeze authority
+        instructions.push(
+          new anchor.web3.TransactionInstruction({
+            programId: tokenProgram,
+            keys: [{ pubkey: mint, isSigner: false, isWritable: true }],
+            data: Buffer.concat([Buffer.from([20, 6]), authority.publicKey.toBuffer(), Buffer.from([0])]),
+          })
+        );
+        return instructions;
+      }
+    );
+  };
+
+  const createTokenAccount = (
+    payer: anchor.web3.Keypair,
+    mint: PublicKey,
+    owner: PublicKey,
+    tokenProgram: PublicKey,
+    space = TOKEN_ACCOUNT_SIZE
+  ) =>
+    createTokenProgramAccount(payer, space, tokenProgram, (account) => [
+      // InitializeAccount3
+      new anchor.web3.TransactionInstruction({
+        programId: tokenProgram,
+        keys: [
+          { pubkey: account, isSigner: false, isWritable: true },
+          { pubkey: mint, isSigner: false, isWritable: false },
+        ],
+        data: Buffer.concat([Buffer.from([18]), owner.toBuffer()]),
+      }),
+    ]);
+
+  const mintTokens = async (
+    authority: anchor.web3.Keypair,
+    mint: PublicKey,
+    destination: PublicKey,
+    tokenProgram: PublicKey,
+    amount: number
+  ) => {
+    // MintTo
+    const data = Buffer.concat([Buffer.from([7]), new anchor.BN(amount).toArrayLike(Buffer, "le", 8)]);
+    const tx = new anchor.web3.Transaction().add(
+      new anchor.web3.TransactionInstruction({
+        programId: tokenProgram,
+        keys: [
+          { pubkey: mint, isSigner: false, isWritable: true },
+          { pubkey: destination, isSigner: false, isWritable: true },
+          { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        ],
+        data,
+      })
+    );
+    await provider.sendAndConfirm(tx, [authority]);
+  };
+
+  const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+  const tokenBalance = async (account: PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(account)).value.amount);
+
+  // Events emitted by a transaction sent with "confirmed" commitment
+  const eventsOf = async (signature: string) => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return [...parser.parseLogs(tx.meta.logMessages)];
+  };
 
   before(async () => {
     console.log("\nSetting up full integration test environment...\n");
@@ -77,12 +225,12 @@ describe("Full Integration Test", () => {
     );
 
     [escrowPda] = PublicKey.findProgramAddressSync(
-      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer()],
+      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer(), escrowIdSeed(ESCROW_ID)],
       program.programId
     );
 
     [escrow2Pda] = PublicKey.findProgramAddressSync(
-      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer()],
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(ESCROW_ID_2)],
       program.programId
     );
 
@@ -121,7 +269,7 @@ describe("Full Integration Test", () => {
     console.log("Initializing platform configuration...");
 
     await program.methods
-      .initializeConfig(FEE_BASIS_POINTS)
+      .initializeConfig(FEE_BASIS_POINTS, ARBITER_FEE_BASIS_POINTS, new anchor.BN(MIN_ARBITER_FEE))
       .accounts({
         admin: admin.publicKey,
         feeCollector: feeCollectorPda,
@@ -133,10 +281,139 @@ describe("Full Integration Test", () => {
 
     assert.ok(config.admin.equals(admin.publicKey));
     assert.equal(config.feeBasisPoints, FEE_BASIS_POINTS);
+    assert.equal(config.arbiterFeeBasisPoints, ARBITER_FEE_BASIS_POINTS);
+    assert.equal(config.minArbiterFee.toNumber(), MIN_ARBITER_FEE);
 
     console.log(`Config initialized with ${FEE_BASIS_POINTS / 100}% platform fee\n`);
   });
 
+  it("Step 1b: Admin updates fee settings", async () => {
+    console.log("Updating platform configuration...");
+
+    // Raise the arbiter fee, then restore it, leaving other settings untouched
+    await program.methods
+      .updateConfig({
+        feeBasisPoints: null,
+        arbiterFeeBasisPoints: 200,
+        minArbiterFee: null,
+        buyerDisputeFeeBasisPoints: null,
+        sellerDisputeFeeBasisPoints: null,
+        splitDisputeFeeBasisPoints: null,
+        panelThreshold: null,
+        panelSize: null,
+        votingPeriod: null,
+        appealBond: null,
+        appealWindow: null,
+        minArbiterStake: null,
+        stakeCooldown: null,
+        overturnSlash: null,
+        disputeWindow: null,
+        escrowTimeout: null,
+      })
+      .accounts({
+        config: configPda,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+
+    let config = await program.account.config.fetch(configPda);
+    assert.equal(config.arbiterFeeBasisPoints, 200);
+    assert.equal(config.feeBasisPoints, FEE_BASIS_POINTS);
+
+    // Also close the appeal window so rulings execute right away, Step 10d reopens it
+    await program.methods
+      .updateConfig({
+        feeBasisPoints: null,
+        arbiterFeeBasisPoints: ARBITER_FEE_BASIS_POINTS,
+        minArbiterFee: null,
+        buyerDisputeFeeBasisPoints: null,
+        sellerDisputeFeeBasisPoints: null,
+        splitDisputeFeeBasisPoints: null,
+        panelThreshold: null,
+        panelSize: null,
+        votingPeriod: null,
+        appealBond: null,
+        appealWindow: 0,
+        minArbiterStake: null,
+        stakeCooldown: null,
+        overturnSlash: null,
+        disputeWindow: null,
+        escrowTimeout: null,
+      })
+      .accounts({
+        config: configPda,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
+    assert.equal(config.arbiterFeeBasisPoints, ARBITER_FEE_BASIS_POINTS);
+    assert.equal(config.appealWindow, 0);
+
+    console.log("Config updated and restored\n");
+  });
+
+  it("Step 1c: Guardian toggles the pause flags", async () => {
+    console.log("Assigning a guardian and pausing the program...");
+
+    const guardian = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .setGuardian(guardian.publicKey)
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .setPause(true, true)
+      .accounts({ config: configPda, authority: guardian.publicKey } as any)
+      .signers([guardian])
+      .rpc();
+
+    let config = await program.account.config.fetch(configPda);
+    assert.ok(config.guardian.equals(guardian.publicKey));
+    assert.isTrue(config.escrowsPaused);
+    assert.isTrue(config.emergencyMode);
+
+    // Resume normal operation for the rest of the test
+    await program.methods
+      .setPause(false, false)
+      .accounts({ config: configPda, authority: guardian.publicKey } as any)
+      .signers([guardian])
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
+    assert.isFalse(config.escrowsPaused);
+    assert.isFalse(config.emergencyMode);
+
+    console.log("Program paused and resumed by guardian\n");
+  });
+
+  it("Step 1d: Admin sets reputation fee tiers", async () => {
+    console.log("Setting reputation fee tiers...");
+
+    // Experienced sellers pay less, none of the test sellers qualify yet
+    const feeTiers = [
+      { minSuccessfulTrades: new anchor.BN(10), minSuccessRateBps: 9000, feeBasisPoints: 200 },
+      { minSuccessfulTrades: new anchor.BN(100), minSuccessRateBps: 9500, feeBasisPoints: 100 },
+    ];
+
+    await program.methods
+      .setFeeTiers(feeTiers)
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const config = await program.account.config.fetch(configPda);
+    assert.equal(config.feeTiers.length, 2);
+    assert.equal(config.feeTiers[1].minSuccessfulTrades.toNumber(), 100);
+    assert.equal(config.feeTiers[1].feeBasisPoints, 100);
+
+    console.log("Fee tiers configured\n");
+  });
+
   it("Step 2: Add arbiter to the platform", async () => {
     console.log("Adding arbiter to the platform...");
 
@@ -150,12 +427,20 @@ describe("Full Integration Test", () => {
       .signers([admin])
       .rpc();
 
+    // Arbiters need the minimum stake before they can rule on disputes
+    await program.methods
+      .depositStake(new anchor.BN(MIN_ARBITER_STAKE))
+      .accounts({ arbiter: arbiter.publicKey } as any)
+      .signers([arbiter])
+      .rpc();
+
     const arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
 
     assert.ok(arbiterAccount.arbiter.equals(arbiter.publicKey));
     assert.ok(arbiterAccount.isActive);
+    assert.equal(arbiterAccount.stake.toNumber(), MIN_ARBITER_STAKE);
 
-    console.log("Arbiter added and activated\n");
+    console.log("Arbiter added, staked and activated\n");
   });
 
   it("Step 3: Initialize reputation accounts", async () => {
@@ -183,27 +468,7 @@ describe("Full Integration Test", () => {
       .signers([seller])
       .rpc();
 
-    // Initialize buyer2 reputation
-    await program.methods
-      .initializeReputation()
-      .accounts({
-        reputation: buyer2ReputationPda,
-        user: buyer2.publicKey,
-        payer: buyer2.publicKey,
-      } as any)
-      .signers([buyer2])
-      .rpc();
-
-    // Initialize seller2 reputation
-    await program.methods
-      .initializeReputation()
-      .accounts({
-        reputation: seller2ReputationPda,
-        user: seller2.publicKey,
-        payer: seller2.publicKey,
-      } as any)
-      .signers([seller2])
-      .rpc();
+    // Buyer 2 and Seller 2 get theirs automatically when their escrow is created (Step 5)
 
     const buyerRep = await program.account.reputation.fetch(buyerReputationPda);
     const sellerRep = await program.account.reputation.fetch(sellerReputationPda);
@@ -222,11 +487,12 @@ describe("Full Integration Test", () => {
     const initialBuyerBalance = await provider.connection.getBalance(buyer.publicKey);
 
     await program.methods
-      .createEscrow(new anchor.BN(ESCROW_AMOUNT))
+      .createEscrow(ESCROW_ID, new anchor.BN(ESCROW_AMOUNT), [], [], true)
       .accounts({
         escrow: escrowPda,
         buyer: buyer.publicKey,
         seller: seller.publicKey,
+        config: configPda,
       } as any)
       .signers([buyer])
       .rpc();
@@ -237,7 +503,7 @@ describe("Full Integration Test", () => {
     assert.ok(escrow.buyer.equals(buyer.publicKey));
     assert.ok(escrow.seller.equals(seller.publicKey));
     assert.equal(escrow.amount.toNumber(), ESCROW_AMOUNT);
-    assert.deepEqual(escrow.status, { active: {} });
+    assert.deepEqual(escrow.status, { initialized: {} });
     assert.isTrue(initialBuyerBalance - finalBuyerBalance >= ESCROW_AMOUNT);
 
     console.log("Escrow 1 created successfully\n");
@@ -247,11 +513,12 @@ describe("Full Integration Test", () => {
     console.log(`Creating second escrow for ${ESCROW_AMOUNT_2 / LAMPORTS_PER_SOL} SOL...`);
 
     await program.methods
-      .createEscrow(new anchor.BN(ESCROW_AMOUNT_2))
+      .createEscrow(ESCROW_ID_2, new anchor.BN(ESCROW_AMOUNT_2), [], [arbiter.publicKey], false)
       .accounts({
         escrow: escrow2Pda,
         buyer: buyer2.publicKey,
         seller: seller2.publicKey,
+        config: configPda,
       } as any)
       .signers([buyer2])
       .rpc();
@@ -261,10 +528,109 @@ describe("Full Integration Test", () => {
     assert.ok(escrow2.buyer.equals(buyer2.publicKey));
     assert.ok(escrow2.seller.equals(seller2.publicKey));
     assert.equal(escrow2.amount.toNumber(), ESCROW_AMOUNT_2);
+    assert.equal(escrow2.arbiters.length, 1);
+    assert.ok(escrow2.arbiters[0].equals(arbiter.publicKey));
+
+    // Reputation accounts were created for both parties, paid by the buyer
+    const buyer2Rep = await program.account.reputation.fetch(buyer2ReputationPda);
+    const seller2Rep = await program.account.reputation.fetch(seller2ReputationPda);
+    assert.ok(buyer2Rep.user.equals(buyer2.publicKey));
+    assert.ok(seller2Rep.user.equals(seller2.publicKey));
+    assert.equal(seller2Rep.successfulTrades.toNumber(), 0);
 
     console.log("Escrow 2 created successfully\n");
   });
 
+  it("Step 5b: Open a concurrent escrow between Buyer 1 and Seller 1", async () => {
+    console.log("Creating a second escrow for the same buyer/seller pair...");
+
+    const concurrentEscrowId = new anchor.BN(2);
+    [concurrentEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer(), escrowIdSeed(concurrentEscrowId)],
+      program.programId
+    );
+
+    await program.methods
+      .createEscrow(concurrentEscrowId, new anchor.BN(ESCROW_AMOUNT_2), [], [], false)
+      .accounts({
+        escrow: concurrentEscrowPda,
+        buyer: buyer.publicKey,
+        seller: seller.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer])
+      .rpc();
+
+    const concurrentEscrow = await program.account.escrow.fetch(concurrentEscrowPda);
+    const firstEscrow = await program.account.escrow.fetch(escrowPda);
+
+    assert.equal(concurrentEscrow.escrowId.toNumber(), 2);
+    assert.equal(firstEscrow.escrowId.toNumber(), 1);
+    assert.equal(concurrentEscrow.amount.toNumber(), ESCROW_AMOUNT_2);
+
+    console.log("Concurrent escrow created alongside Escrow 1\n");
+  });
+
+  it("Step 5c: Buyer 1 cancels the concurrent escrow before acceptance", async () => {
+    console.log("Buyer 1 cancelling the unaccepted concurrent escrow...");
+
+    const initialBuyerBalance = await provider.connection.getBalance(buyer.publicKey);
+
+    await program.methods
+      .cancelEscrow()
+      .accounts({
+        escrow: concurrentEscrowPda,
+        buyer: buyer.publicKey,
+        seller: seller.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer])
+      .rpc();
+
+    const finalBuyerBalance = await provider.connection.getBalance(buyer.publicKey);
+    const concurrentEscrow = await program.account.escrow.fetchNullable(concurrentEscrowPda);
+
+    assert.isNull(concurrentEscrow, "cancelled escrow should be closed");
+    assert.isTrue(finalBuyerBalance - initialBuyerBalance >= ESCROW_AMOUNT_2);
+
+    console.log("Unaccepted escrow cancelled and refunded\n");
+  });
+
+  it("Step 5d: Sellers accept their escrows", async () => {
+    console.log("Sellers accepting escrow terms...");
+
+    await program.methods
+      .acceptEscrow()
+      .accounts({
+        escrow: escrowPda,
+        seller: seller.publicKey,
+        buyer: buyer.publicKey,
+        config: configPda,
+      } as any)
+      .signers([seller])
+      .rpc();
+
+    await program.methods
+      .acceptEscrow()
+      .accounts({
+        escrow: escrow2Pda,
+        seller: seller2.publicKey,
+        buyer: buyer2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([seller2])
+      .rpc();
+
+    const escrow = await program.account.escrow.fetch(escrowPda);
+    const escrow2 = await program.account.escrow.fetch(escrow2Pda);
+
+    assert.deepEqual(escrow.status, { active: {} });
+    assert.deepEqual(escrow2.status, { active: {} });
+    assert.isTrue(escrow.acceptedAt.toNumber() > 0);
+
+    console.log("Both escrows accepted and active\n");
+  });
+
   it("Step 6: Release funds from first escrow (with platform fee)", async () => {
     console.log("Buyer 1 releasing funds to Seller 1...");
 
@@ -287,12 +653,13 @@ describe("Full Integration Test", () => {
 
     const finalSellerBalance = await provider.connection.getBalance(seller.publicKey);
     const finalFeeCollectorBalance = await provider.connection.getBalance(feeCollectorPda);
-    const escrow = await program.account.escrow.fetch(escrowPda);
+    const escrow = await program.account.escrow.fetchNullable(escrowPda);
 
     // Calculate expected fee and seller amount
     const expectedFee = (ESCROW_AMOUNT * FEE_BASIS_POINTS) / 10_000;
     const expectedSellerAmount = ESCROW_AMOUNT - expectedFee;
 
+    // Escrow 1 was created with keep_open, so it stays around for audit
     assert.deepEqual(escrow.status, { completed: {} });
     assert.equal(finalSellerBalance - initialSellerBalance, expectedSellerAmount);
     assert.equal(finalFeeCollectorBalance - initialFeeCollectorBalance, expectedFee);
@@ -309,16 +676,42 @@ describe("Full Integration Test", () => {
     console.log(`   Buyer successful trades: ${buyerRep.successfulTrades}, Seller successful trades: ${sellerRep.successfulTrades}\n`);
   });
 
+  it("Step 6b: Seller closes the settled escrow kept open for audit", async () => {
+    console.log("Seller 1 closing Escrow 1...");
+
+    const initialBuyerBalance = await provider.connection.getBalance(buyer.publicKey);
+    const escrowRent = await provider.connection.getBalance(escrowPda);
+
+    await program.methods
+      .closeEscrow()
+      .accounts({
+        escrow: escrowPda,
+        party: seller.publicKey,
+        buyer: buyer.publicKey,
+        seller: seller.publicKey,
+      } as any)
+      .signers([seller])
+      .rpc();
+
+    // The rent goes back to the buyer who funded the escrow
+    const finalBuyerBalance = await provider.connection.getBalance(buyer.publicKey);
+    assert.isNull(await program.account.escrow.fetchNullable(escrowPda));
+    assert.equal(finalBuyerBalance - initialBuyerBalance, escrowRent);
+
+    console.log(`Escrow 1 closed, ${escrowRent / LAMPORTS_PER_SOL} SOL rent returned to Buyer 1\n`);
+  });
+
   it("Step 7: Raise dispute on second escrow", async () => {
     console.log("Buyer 2 raising a dispute...");
 
     await program.methods
-      .raiseDispute()
+      .raiseDispute({ notDelivered: {} })
       .accounts({
         escrow: escrow2Pda,
         party: buyer2.publicKey,
         buyer: buyer2.publicKey,
         seller: seller2.publicKey,
+        config: configPda,
       } as any)
       .signers([buyer2])
       .rpc();
@@ -326,14 +719,85 @@ describe("Full Integration Test", () => {
     const escrow2 = await program.account.escrow.fetch(escrow2Pda);
 
     assert.deepEqual(escrow2.status, { disputed: {} });
+    assert.deepEqual(escrow2.disputeReason, { notDelivered: {} });
+    assert.ok(escrow2.disputedBy.equals(buyer2.publicKey));
+
+    // Once disputed, not even both parties can pull the funds back, only a ruling settles them
+    try {
+      await program.methods
+        .refundBuyer()
+        .accounts({
+          escrow: escrow2Pda,
+          buyer: buyer2.publicKey,
+          seller: seller2.publicKey,
+          config: configPda,
+          feeCollector: feeCollectorPda,
+          buyerReputation: buyer2ReputationPda,
+          sellerReputation: seller2ReputationPda,
+        } as any)
+        .signers([buyer2, seller2])
+        .rpc();
+      assert.fail("disputed escrow should not be refundable");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidState");
+    }
 
     console.log("Dispute raised on Escrow 2\n");
   });
 
+  it("Step 7b: Buyer and arbiter submit dispute evidence", async () => {
+    console.log("Submitting evidence for the dispute...");
+
+    const { createdAt } = await program.account.escrow.fetch(escrow2Pda);
+    const evidencePda = (index: number) => evidencePdaFor(escrow2Pda, createdAt, index);
+
+    const buyerHash = createHash("sha256").update("shipping-tracking.pdf").digest();
+    await program.methods
+      .submitEvidence([...buyerHash], "ipfs://buyer-shipping-tracking", { delivery: {} })
+      .accounts({
+        escrow: escrow2Pda,
+        evidence: evidencePda(0),
+        submitter: buyer2.publicKey,
+        arbiterAccount: null,
+        buyer: buyer2.publicKey,
+        seller: seller2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer2])
+      .rpc();
+
+    const arbiterHash = createHash("sha256").update("arbiter-notes.txt").digest();
+    await program.methods
+      .submitEvidence([...arbiterHash], "ipfs://arbiter-notes", { other: {} })
+      .accounts({
+        escrow: escrow2Pda,
+        evidence: evidencePda(1),
+        submitter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        buyer: buyer2.publicKey,
+        seller: seller2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([arbiter])
+      .rpc();
+
+    const buyerEvidence = await program.account.evidence.fetch(evidencePda(0));
+    const escrow2 = await program.account.escrow.fetch(escrow2Pda);
+
+    assert.ok(buyerEvidence.submittedBy.equals(buyer2.publicKey));
+    assert.deepEqual(Buffer.from(buyerEvidence.contentHash), buyerHash);
+    assert.equal(buyerEvidence.uri, "ipfs://buyer-shipping-tracking");
+    assert.equal(escrow2.evidenceCount, 2);
+
+    console.log("Evidence recorded on-chain\n");
+  });
+
   it("Step 8: Arbiter resolves dispute in favor of buyer", async () => {
     console.log("Arbiter resolving dispute...");
 
     const initialBuyer2Balance = await provider.connection.getBalance(buyer2.publicKey);
+    const initialArbiterBalance = await provider.connection.getBalance(arbiter.publicKey);
+    const initialFeeCollectorBalance = await provider.connection.getBalance(feeCollectorPda);
 
     await program.methods
       .resolveDispute({ favorBuyer: {} })
@@ -341,20 +805,57 @@ describe("Full Integration Test", () => {
         escrow: escrow2Pda,
         arbiter: arbiter.publicKey,
         arbiterAccount: arbiterPda,
+        config: configPda,
+        buyer: buyer2.publicKey,
+        seller: seller2.publicKey,
+      } as any)
+      .signers([arbiter])
+      .rpc();
+
+    const ruledEscrow = await program.account.escrow.fetch(escrow2Pda);
+    assert.deepEqual(ruledEscrow.status, { ruled: {} });
+    assert.deepEqual(ruledEscrow.pendingResolution, { favorBuyer: {} });
+
+    // The ruling is added to the arbiter's statistics
+    const arbiterStats = await program.account.arbiter.fetch(arbiterPda);
+    assert.equal(arbiterStats.disputesResolved.toNumber(), 1);
+    assert.equal(arbiterStats.favorBuyerRulings.toNumber(), 1);
+    assert.equal(arbiterStats.appealsLost.toNumber(), 0);
+    assert.isTrue(arbiterStats.lastActiveAt.toNumber() >= ruledEscrow.disputedAt.toNumber());
+
+    // With the appeal window closed anyone can carry out the ruling at once
+    await program.methods
+      .executeRuling()
+      .accounts({
+        escrow: escrow2Pda,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        config: configPda,
+        feeCollector: feeCollectorPda,
         buyer: buyer2.publicKey,
         seller: seller2.publicKey,
         buyerReputation: buyer2ReputationPda,
         sellerReputation: seller2ReputationPda,
       } as any)
-      .signers([arbiter])
       .rpc();
 
     const finalBuyer2Balance = await provider.connection.getBalance(buyer2.publicKey);
-    const escrow2 = await program.account.escrow.fetch(escrow2Pda);
+    const finalArbiterBalance = await provider.connection.getBalance(arbiter.publicKey);
+    const finalFeeCollectorBalance = await provider.connection.getBalance(feeCollectorPda);
+    const escrow2 = await program.account.escrow.fetchNullable(escrow2Pda);
+    const expectedArbiterFee = Math.max(
+      (ESCROW_AMOUNT_2 * ARBITER_FEE_BASIS_POINTS) / 10_000,
+      MIN_ARBITER_FEE
+    );
+    // Buyer-favored disputes default to the regular platform fee
+    const expectedPlatformFee = (ESCROW_AMOUNT_2 * FEE_BASIS_POINTS) / 10_000;
+    const expectedRefund = ESCROW_AMOUNT_2 - expectedArbiterFee - expectedPlatformFee;
 
-    // Check buyer got refund
-    assert.equal(finalBuyer2Balance - initialBuyer2Balance, ESCROW_AMOUNT_2);
-    assert.deepEqual(escrow2.status, { completed: {} });
+    // Check arbiter and platform were paid and buyer got the rest plus the escrow rent back
+    assert.equal(finalArbiterBalance - initialArbiterBalance, expectedArbiterFee);
+    assert.equal(finalFeeCollectorBalance - initialFeeCollectorBalance, expectedPlatformFee);
+    assert.isTrue(finalBuyer2Balance - initialBuyer2Balance > expectedRefund);
+    assert.isNull(escrow2, "resolved escrow should be closed");
 
     // Check reputation updates (buyer gets +1 successful, seller gets +1 failed)
     const buyer2Rep = await program.account.reputation.fetch(buyer2ReputationPda);
@@ -363,7 +864,9 @@ describe("Full Integration Test", () => {
     assert.equal(buyer2Rep.successfulTrades.toNumber(), 1);
     assert.equal(seller2Rep.failedTrades.toNumber(), 1);
 
-    console.log(`Dispute resolved: Buyer 2 refunded ${ESCROW_AMOUNT_2 / LAMPORTS_PER_SOL} SOL`);
+    console.log(`Dispute resolved: Buyer 2 refunded ${expectedRefund / LAMPORTS_PER_SOL} SOL`);
+    console.log(`   Arbiter fee paid: ${expectedArbiterFee / LAMPORTS_PER_SOL} SOL`);
+    console.log(`   Platform fee paid: ${expectedPlatformFee / LAMPORTS_PER_SOL} SOL`);
     console.log(`   Buyer 2 successful trades: ${buyer2Rep.successfulTrades}, Seller 2 failed trades: ${seller2Rep.failedTrades}\n`);
   });
 
@@ -404,52 +907,1861 @@ describe("Full Integration Test", () => {
     console.log("Verifying reputation states...");
 
     const buyerRep = await program.account.reputation.fetch(buyerReputationPda);
+    const buyer2Rep = await program.account.reputation.fetch(buyer2ReputationPda);
     const seller2Rep = await program.account.reputation.fetch(seller2ReputationPda);
 
     // Buyer should have 1 successful trade from Step 6
     assert.equal(buyerRep.successfulTrades.toNumber(), 1);
     assert.equal(buyerRep.failedTrades.toNumber(), 0);
+    assert.equal(buyerRep.totalVolume.toNumber(), ESCROW_AMOUNT);
+    assert.equal(buyerRep.disputedVolume.toNumber(), 0);
 
-    // Seller 2 should have 1 failed trade from Step 8
+    // Seller 2 should have 1 failed trade and a lost dispute from Step 8
     assert.equal(seller2Rep.successfulTrades.toNumber(), 0);
     assert.equal(seller2Rep.failedTrades.toNumber(), 1);
+    assert.equal(seller2Rep.totalVolume.toNumber(), 0);
+    assert.equal(seller2Rep.disputedVolume.toNumber(), ESCROW_AMOUNT_2);
+    assert.equal(seller2Rep.disputesLost.toNumber(), 1);
+
+    // Buyer 2 raised and won that dispute, so only their volume counts
+    assert.equal(buyer2Rep.totalVolume.toNumber(), ESCROW_AMOUNT_2);
+    assert.equal(buyer2Rep.disputesRaised.toNumber(), 1);
+    assert.equal(buyer2Rep.disputesLost.toNumber(), 0);
+    assert.isTrue(buyer2Rep.lastActivityAt.toNumber() > 0);
 
     console.log(`Reputation verified: Buyer 1 = ${buyerRep.successfulTrades} successful, Seller 2 = ${seller2Rep.failedTrades} failed\n`);
   });
 
-  it("Step 11: Remove arbiter from platform", async () => {
-    console.log("Admin removing arbiter...");
+  it("Step 10a: Migrate a reputation account created with the old layout", async () => {
+    console.log("Migrating a pre-volume reputation account...");
+
+    // Loaded into the validator from tests/fixtures/legacy_reputation.json
+    const legacyUser = new PublicKey(Buffer.alloc(32, 7));
+    const [legacyReputationPda] = PublicKey.findProgramAddressSync(
+      [REPUTATION_SEED, legacyUser.toBuffer()],
+      program.programId
+    );
+
+    const legacyAccount = await provider.connection.getAccountInfo(legacyReputationPda);
+    assert.equal(legacyAccount.data.length, 64);
 
     await program.methods
-      .removeArbiter()
+      .migrateReputation()
       .accounts({
-        config: configPda,
-        admin: admin.publicKey,
-        arbiter: arbiter.publicKey,
+        reputation: legacyReputationPda,
+        user: legacyUser,
+        payer: admin.publicKey,
       } as any)
       .signers([admin])
       .rpc();
 
-    const arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
+    const migratedAccount = await provider.connection.getAccountInfo(legacyReputationPda);
+    const newSize = program.account.reputation.size;
+    assert.equal(migratedAccount.data.length, newSize);
+    assert.isTrue(
+      migratedAccount.lamports >= (await provider.connection.getMinimumBalanceForRentExemption(newSize))
+    );
 
-    assert.isFalse(arbiterAccount.isActive);
+    // Existing counters survive, the new fields start at zero
+    const legacyRep = await program.account.reputation.fetch(legacyReputationPda);
+    assert.ok(legacyRep.user.equals(legacyUser));
+    assert.equal(legacyRep.successfulTrades.toNumber(), 3);
+    assert.equal(legacyRep.failedTrades.toNumber(), 1);
+    assert.equal(legacyRep.totalVolume.toNumber(), 0);
+    assert.equal(legacyRep.disputesLost.toNumber(), 0);
+
+    // A second migration has nothing to do
+    try {
+      await program.methods
+        .migrateReputation()
+        .accounts({
+          reputation: legacyReputationPda,
+          user: legacyUser,
+          payer: admin.publicKey,
+        } as any)
+        .signers([admin])
+        .rpc();
+      assert.fail("Migrating twice should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyMigrated");
+    }
 
-    console.log("Arbiter deactivated\n");
+    console.log(`Reputation account grew from ${legacyAccount.data.length} to ${migratedAccount.data.length} bytes\n`);
   });
 
-  it("Step 12: Verify final system state", async () => {
-    console.log("Verifying final system state...");
+  it("Step 10b: Release a milestone escrow one milestone at a time", async () => {
+    console.log("Buyer 2 funding a two-milestone escrow for Seller 2...");
 
-    // Check config
-    const config = await program.account.config.fetch(configPda);
-    assert.ok(config.admin.equals(admin.publicKey));
-    assert.equal(config.feeBasisPoints, FEE_BASIS_POINTS);
+    const milestoneEscrowId = new anchor.BN(2);
+    const milestones = [new anchor.BN(LAMPORTS_PER_SOL), new anchor.BN(2 * LAMPORTS_PER_SOL)];
+    const [milestoneEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(milestoneEscrowId)],
+      program.programId
+    );
 
-    // Check escrow states
-    const escrow1 = await program.account.escrow.fetch(escrowPda);
-    const escrow2 = await program.account.escrow.fetch(escrow2Pda);
-    assert.deepEqual(escrow1.status, { completed: {} });
-    assert.deepEqual(escrow2.status, { completed: {} });
+    await program.methods
+      .createEscrow(milestoneEscrowId, new anchor.BN(3 * LAMPORTS_PER_SOL), milestones, [], false)
+      .accounts({
+        escrow: milestoneEscrowPda,
+        buyer: buyer2.publicKey,
+        seller: seller2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer2])
+      .rpc();
+
+    await program.methods
+      .acceptEscrow()
+      .accounts({
+        escrow: milestoneEscrowPda,
+        seller: seller2.publicKey,
+        buyer: buyer2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([seller2])
+      .rpc();
+
+    const releaseAccounts = {
+      escrow: milestoneEscrowPda,
+      buyer: buyer2.publicKey,
+      seller: seller2.publicKey,
+      buyerReputation: buyer2ReputationPda,
+      sellerReputation: seller2ReputationPda,
+      config: configPda,
+      feeCollector: feeCollectorPda,
+    };
+
+    const initialSellerBalance = await provider.connection.getBalance(seller2.publicKey);
+
+    await program.methods
+      .releaseFunds()
+      .accounts(releaseAccounts as any)
+      .signers([buyer2])
+      .rpc();
+
+    const partialEscrow = await program.account.escrow.fetch(milestoneEscrowPda);
+    const afterFirstBalance = await provider.connection.getBalance(seller2.publicKey);
+    const firstFee = (LAMPORTS_PER_SOL * FEE_BASIS_POINTS) / 10_000;
+
+    assert.equal(partialEscrow.milestonesReleased, 1);
+    assert.equal(partialEscrow.releasedAmount.toNumber(), LAMPORTS_PER_SOL);
+    assert.deepEqual(partialEscrow.status, { active: {} });
+    assert.equal(afterFirstBalance - initialSellerBalance, LAMPORTS_PER_SOL - firstFee);
+
+    await program.methods
+      .releaseFunds()
+      .accounts(releaseAccounts as any)
+      .signers([buyer2])
+      .rpc();
+
+    const completedEscrow = await program.account.escrow.fetchNullable(milestoneEscrowPda);
+    assert.isNull(completedEscrow, "escrow should close after the final milestone");
+
+    console.log("Both milestones released and escrow closed\n");
+  });
+
+  it("Step 10c: A panel of arbiters decides a high-value dispute", async () => {
+    console.log("Setting up a three-arbiter panel...");
+
+    const panelist2 = anchor.web3.Keypair.generate();
+    const panelist3 = anchor.web3.Keypair.generate();
+    for (const panelist of [panelist2, panelist3]) {
+      const airdrop = await provider.connection.requestAirdrop(panelist.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(airdrop);
+      await program.methods
+        .addArbiter()
+        .accounts({ config: configPda, admin: admin.publicKey, arbiter: panelist.publicKey } as any)
+        .signers([admin])
+        .rpc();
+      await program.methods
+        .depositStake(new anchor.BN(MIN_ARBITER_STAKE))
+        .accounts({ arbiter: panelist.publicKey } as any)
+        .signers([panelist])
+        .rpc();
+    }
+
+    const configUpdate = (panelThreshold: anchor.BN) => ({
+      feeBasisPoints: null,
+      arbiterFeeBasisPoints: null,
+      minArbiterFee: null,
+      buyerDisputeFeeBasisPoints: null,
+      sellerDisputeFeeBasisPoints: null,
+      splitDisputeFeeBasisPoints: null,
+      panelThreshold,
+      panelSize: null,
+      votingPeriod: null,
+      appealBond: null,
+      appealWindow: null,
+      minArbiterStake: null,
+      stakeCooldown: null,
+      overturnSlash: null,
+      disputeWindow: null,
+      escrowTimeout: null,
+    });
+
+    // Disputes over 1 SOL or more go to a panel
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(LAMPORTS_PER_SOL)))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const panelEscrowId = new anchor.BN(3);
+    const panelAmount = 2 * LAMPORTS_PER_SOL;
+    const [panelEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer(), escrowIdSeed(panelEscrowId)],
+      program.programId
+    );
+    const [disputeVotePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_vote"), panelEscrowPda.toBuffer()],
+      program.programId
+    );
+    const arbiterPdaFor = (key: PublicKey) =>
+      PublicKey.findProgramAddressSync([ARBITER_SEED, key.toBuffer()], program.programId)[0];
+    const escrowAccounts = {
+      escrow: panelEscrowPda,
+      buyer: buyer.publicKey,
+      seller: seller.publicKey,
+      config: configPda,
+    };
+
+    // A panel is drawn from the arbiters the parties agree on, so they must name enough of them
+    try {
+      await program.methods
+        .createEscrow(panelEscrowId, new anchor.BN(panelAmount), [], [], false)
+        .accounts(escrowAccounts as any)
+        .signers([buyer])
+        .rpc();
+      assert.fail("A panel escrow without assigned arbiters should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidArbiters");
+    }
+
+    await program.methods
+      .createEscrow(panelEscrowId, new anchor.BN(panelAmount), [], [
+        arbiter.publicKey,
+        panelist2.publicKey,
+        panelist3.publicKey,
+      ], false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notAsDescribed: {} })
+      .accounts({ ...escrowAccounts, party: seller.publicKey } as any)
+      .signers([seller])
+      .rpc();
+
+    const disputedEscrow = await program.account.escrow.fetch(panelEscrowPda);
+    assert.equal(disputedEscrow.panelSize, 3);
+
+    // Two of three votes form a majority
+    for (const voter of [arbiter, panelist2]) {
+      await program.methods
+        .castDisputeVote({ favorSeller: {} })
+        .accounts({
+          ...escrowAccounts,
+          disputeVote: disputeVotePda,
+          arbiter: voter.publicKey,
+          arbiterAccount: arbiterPdaFor(voter.publicKey),
+        } as any)
+        .signers([voter])
+        .rpc();
+    }
+
+    const initialPanelist2Balance = await provider.connection.getBalance(panelist2.publicKey);
+    const initialArbiterStats = await program.account.arbiter.fetch(arbiterPda);
+
+    // The ruling is credited to both voters, their arbiter accounts follow the voting order
+    await program.methods
+      .resolveDispute({ favorSeller: {} })
+      .accounts({
+        ...escrowAccounts,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        disputeVote: disputeVotePda,
+      } as any)
+      .remainingAccounts([
+        { pubkey: arbiterPda, isWritable: true, isSigner: false },
+        { pubkey: arbiterPdaFor(panelist2.publicKey), isWritable: true, isSigner: false },
+      ])
+      .signers([arbiter])
+      .rpc();
+
+    const arbiterStats = await program.account.arbiter.fetch(arbiterPda);
+    assert.equal(
+      arbiterStats.disputesResolved.toNumber(),
+      initialArbiterStats.disputesResolved.toNumber() + 1
+    );
+    assert.equal(arbiterStats.pendingRulings, initialArbiterStats.pendingRulings + 1);
+    const panelist2Stats = await program.account.arbiter.fetch(arbiterPdaFor(panelist2.publicKey));
+    assert.equal(panelist2Stats.disputesResolved.toNumber(), 1);
+    assert.equal(panelist2Stats.favorSellerRulings.toNumber(), 1);
+    assert.equal(panelist2Stats.pendingRulings, 0);
+    assert.isAbove(panelist2Stats.lastActiveAt.toNumber(), 0);
+    const panelist3Stats = await program.account.arbiter.fetch(arbiterPdaFor(panelist3.publicKey));
+    assert.equal(panelist3Stats.disputesResolved.toNumber(), 0);
+
+    await program.methods
+      .executeRuling()
+      .accounts({
+        ...escrowAccounts,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        disputeVote: disputeVotePda,
+        feeCollector: feeCollectorPda,
+        buyerReputation: buyerReputationPda,
+        sellerReputation: sellerReputationPda,
+      } as any)
+      .remainingAccounts([
+        { pubkey: arbiter.publicKey, isWritable: true, isSigner: false },
+        { pubkey: panelist2.publicKey, isWritable: true, isSigner: false },
+      ])
+      .rpc();
+
+    // The arbiter fee is shared between the two voters
+    const finalPanelist2Balance = await provider.connection.getBalance(panelist2.publicKey);
+    const arbiterFee = Math.max((panelAmount * ARBITER_FEE_BASIS_POINTS) / 10_000, MIN_ARBITER_FEE);
+    assert.equal(finalPanelist2Balance - initialPanelist2Balance, arbiterFee / 2);
+    assert.isNull(await program.account.escrow.fetchNullable(panelEscrowPda));
+    assert.isNull(await program.account.disputeVote.fetchNullable(disputeVotePda));
+
+    // Back to single-arbiter disputes
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(0)))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    console.log("Panel majority resolved the dispute in favor of Seller 1\n");
+  });
+
+  it("Step 10d: A frivolous appeal forfeits the bond to the winner", async () => {
+    console.log("Opening the appeal window and a dispute with two assigned arbiters...");
+
+    const appealArbiter = anchor.web3.Keypair.generate();
+    const airdrop = await provider.connection.requestAirdrop(appealArbiter.publicKey, 2 * LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+    await program.methods
+      .addArbiter()
+      .accounts({ config: configPda, admin: admin.publicKey, arbiter: appealArbiter.publicKey } as any)
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .depositStake(new anchor.BN(MIN_ARBITER_STAKE))
+      .accounts({ arbiter: appealArbiter.publicKey } as any)
+      .signers([appealArbiter])
+      .rpc();
+
+    const configUpdate = (appealWindow: number) => ({
+      feeBasisPoints: null,
+      arbiterFeeBasisPoints: null,
+      minArbiterFee: null,
+      buyerDisputeFeeBasisPoints: null,
+      sellerDisputeFeeBasisPoints: null,
+      splitDisputeFeeBasisPoints: null,
+      panelThreshold: null,
+      panelSize: null,
+      votingPeriod: null,
+      appealBond: null,
+      appealWindow,
+      minArbiterStake: null,
+      stakeCooldown: null,
+      overturnSlash: null,
+      disputeWindow: null,
+      escrowTimeout: null,
+    });
+
+    await program.methods
+      .updateConfig(configUpdate(60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const appealEscrowId = new anchor.BN(3);
+    const appealAmount = LAMPORTS_PER_SOL;
+    const [appealEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(appealEscrowId)],
+      program.programId
+    );
+    const escrowAccounts = {
+      escrow: appealEscrowPda,
+      buyer: buyer2.publicKey,
+      seller: seller2.publicKey,
+      config: configPda,
+    };
+
+    await program.methods
+      .createEscrow(appealEscrowId, new anchor.BN(appealAmount), [], [
+        arbiter.publicKey,
+        appealArbiter.publicKey,
+      ], false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer2])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller2])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notDelivered: {} })
+      .accounts({ ...escrowAccounts, party: buyer2.publicKey } as any)
+      .signers([buyer2])
+      .rpc();
+    await program.methods
+      .resolveDispute({ favorBuyer: {} })
+      .accounts({ ...escrowAccounts, arbiter: arbiter.publicKey, arbiterAccount: arbiterPda } as any)
+      .signers([arbiter])
+      .rpc();
+
+    // The ruling can still be overturned, so its arbiter can't start unstaking
+    try {
+      await program.methods
+        .requestUnstake()
+        .accounts({ config: configPda, arbiter: arbiter.publicKey } as any)
+        .signers([arbiter])
+        .rpc();
+      assert.fail("Unstaking with a pending ruling should fail");
+    } catch (err) {
+      assert.include(err.toString(), "PendingRulings");
+    }
+
+    // Seller 2 lost and escalates to the other assigned arbiter
+    const config = await program.account.config.fetch(configPda);
+    const bond = config.appealBond.toNumber();
+    const initialSeller2Balance = await provider.connection.getBalance(seller2.publicKey);
+
+    await program.methods
+      .appealRuling()
+      .accounts({ ...escrowAccounts, appellant: seller2.publicKey } as any)
+      .signers([seller2])
+      .rpc();
+
+    const appealedEscrow = await program.account.escrow.fetch(appealEscrowPda);
+    assert.deepEqual(appealedEscrow.status, { disputed: {} });
+    assert.ok(appealedEscrow.appeal.appellant.equals(seller2.publicKey));
+    assert.equal(appealedEscrow.appeal.bond.toNumber(), bond);
+
+    // The appeal upholds the ruling, so the bond goes to Buyer 2
+    const [appealArbiterPda] = PublicKey.findProgramAddressSync(
+      [ARBITER_SEED, appealArbiter.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .resolveDispute({ favorBuyer: {} })
+      .accounts({
+        ...escrowAccounts,
+        arbiter: appealArbiter.publicKey,
+        arbiterAccount: appealArbiterPda,
+      } as any)
+      .signers([appealArbiter])
+      .rpc();
+
+    const initialBuyer2Balance = await provider.connection.getBalance(buyer2.publicKey);
+
+    await program.methods
+      .executeRuling()
+      .accounts({
+        ...escrowAccounts,
+        arbiter: appealArbiter.publicKey,
+        arbiterAccount: appealArbiterPda,
+        originalArbiterAccount: arbiterPda,
+        originalStakeVault: PublicKey.findProgramAddressSync(
+          [Buffer.from("stake_vault"), arbiter.publicKey.toBuffer()],
+          program.programId
+        )[0],
+        feeCollector: feeCollectorPda,
+        buyerReputation: buyer2ReputationPda,
+        sellerReputation: seller2ReputationPda,
+      } as any)
+      .rpc();
+
+    const finalSeller2Balance = await provider.connection.getBalance(seller2.publicKey);
+    const finalBuyer2Balance = await provider.connection.getBalance(buyer2.publicKey);
+    const arbiterFee = Math.max((appealAmount * ARBITER_FEE_BASIS_POINTS) / 10_000, MIN_ARBITER_FEE);
+    const platformFee = (appealAmount * FEE_BASIS_POINTS) / 10_000;
+
+    assert.equal(initialSeller2Balance - finalSeller2Balance, bond);
+    assert.isTrue(finalBuyer2Balance - initialBuyer2Balance > appealAmount - arbiterFee - platformFee + bond);
+    assert.isNull(await program.account.escrow.fetchNullable(appealEscrowPda));
+
+    await program.methods
+      .updateConfig(configUpdate(0))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    console.log(`Appeal upheld the ruling, Buyer 2 received the ${bond / LAMPORTS_PER_SOL} SOL bond\n`);
+  });
+
+  it("Step 10e: An appeal against a panel ruling closes the panel's votes", async () => {
+    console.log("Appealing a panel ruling to the panelist who didn't vote...");
+
+    const panelist2 = anchor.web3.Keypair.generate();
+    const panelist3 = anchor.web3.Keypair.generate();
+    for (const panelist of [panelist2, panelist3]) {
+      const airdrop = await provider.connection.requestAirdrop(panelist.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(airdrop);
+      await program.methods
+        .addArbiter()
+        .accounts({ config: configPda, admin: admin.publicKey, arbiter: panelist.publicKey } as any)
+        .signers([admin])
+        .rpc();
+      await program.methods
+        .depositStake(new anchor.BN(MIN_ARBITER_STAKE))
+        .accounts({ arbiter: panelist.publicKey } as any)
+        .signers([panelist])
+        .rpc();
+    }
+
+    const configUpdate = (panelThreshold: anchor.BN, votingPeriod: number, appealWindow: number) => ({
+      feeBasisPoints: null,
+      arbiterFeeBasisPoints: null,
+      minArbiterFee: null,
+      buyerDisputeFeeBasisPoints: null,
+      sellerDisputeFeeBasisPoints: null,
+      splitDisputeFeeBasisPoints: null,
+      panelThreshold,
+      panelSize: null,
+      votingPeriod,
+      appealBond: null,
+      appealWindow,
+      minArbiterStake: null,
+      stakeCooldown: null,
+      overturnSlash: null,
+      disputeWindow: null,
+      escrowTimeout: null,
+    });
+
+    // No voting period, so only the first vote counts
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(LAMPORTS_PER_SOL), 0, 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const panelEscrowId = new anchor.BN(4);
+    const [panelEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer(), escrowIdSeed(panelEscrowId)],
+      program.programId
+    );
+    const [disputeVotePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_vote"), panelEscrowPda.toBuffer()],
+      program.programId
+    );
+    const arbiterPdaFor = (key: PublicKey) =>
+      PublicKey.findProgramAddressSync([ARBITER_SEED, key.toBuffer()], program.programId)[0];
+    const escrowAccounts = {
+      escrow: panelEscrowPda,
+      buyer: buyer.publicKey,
+      seller: seller.publicKey,
+      config: configPda,
+    };
+
+    await program.methods
+      .createEscrow(panelEscrowId, new anchor.BN(2 * LAMPORTS_PER_SOL), [], [
+        arbiter.publicKey,
+        panelist2.publicKey,
+        panelist3.publicKey,
+      ], false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notAsDescribed: {} })
+      .accounts({ ...escrowAccounts, party: seller.publicKey } as any)
+      .signers([seller])
+      .rpc();
+
+    const castVote = (voter: anchor.web3.Keypair, resolution: any) =>
+      program.methods
+        .castDisputeVote(resolution)
+        .accounts({
+          ...escrowAccounts,
+          disputeVote: disputeVotePda,
+          arbiter: voter.publicKey,
+          arbiterAccount: arbiterPdaFor(voter.publicKey),
+        } as any)
+        .signers([voter])
+        .rpc();
+
+    // A panel nobody voted on still takes a vote after the deadline, then it is closed
+    await castVote(arbiter, { favorSeller: {} });
+    try {
+      await castVote(panelist2, { favorBuyer: {} });
+      assert.fail("Voting after the deadline should fail");
+    } catch (err) {
+      assert.include(err.toString(), "VotingClosed");
+    }
+    await program.methods
+      .resolveDispute({ favorSeller: {} })
+      .accounts({
+        ...escrowAccounts,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        disputeVote: disputeVotePda,
+      } as any)
+      .remainingAccounts([{ pubkey: arbiterPda, isWritable: true, isSigner: false }])
+      .signers([arbiter])
+      .rpc();
+
+    // Buyer 1 lost and appeals to an assigned arbiter who didn't vote
+    await program.methods
+      .appealRuling()
+      .accounts({ ...escrowAccounts, appellant: buyer.publicKey, disputeVote: disputeVotePda } as any)
+      .signers([buyer])
+      .rpc();
+    await program.methods
+      .resolveDispute({ favorSeller: {} })
+      .accounts({
+        ...escrowAccounts,
+        arbiter: panelist3.publicKey,
+        arbiterAccount: arbiterPdaFor(panelist3.publicKey),
+        disputeVote: disputeVotePda,
+      } as any)
+      .signers([panelist3])
+      .rpc();
+
+    const executeAccounts = {
+      ...escrowAccounts,
+      arbiter: panelist3.publicKey,
+      arbiterAccount: arbiterPdaFor(panelist3.publicKey),
+      originalArbiterAccount: arbiterPda,
+      originalStakeVault: PublicKey.findProgramAddressSync(
+        [Buffer.from("stake_vault"), arbiter.publicKey.toBuffer()],
+        program.programId
+      )[0],
+      feeCollector: feeCollectorPda,
+      buyerReputation: buyerReputationPda,
+      sellerReputation: sellerReputationPda,
+    };
+    const panelVoters = [{ pubkey: arbiter.publicKey, isWritable: true, isSigner: false }];
+
+    // The votes can't be left behind when the escrow closes
+    try {
+      await program.methods
+        .executeRuling()
+        .accounts({ ...executeAccounts, disputeVote: null } as any)
+        .remainingAccounts(panelVoters)
+        .rpc();
+      assert.fail("Executing a panel appeal without its votes should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPanelAccounts");
+    }
+
+    await program.methods
+      .executeRuling()
+      .accounts({ ...executeAccounts, disputeVote: disputeVotePda } as any)
+      .remainingAccounts(panelVoters)
+      .rpc();
+
+    assert.isNull(await program.account.escrow.fetchNullable(panelEscrowPda));
+    assert.isNull(await program.account.disputeVote.fetchNullable(disputeVotePda));
+
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(0), 3 * 24 * 60 * 60, 0))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    console.log("Appeal upheld the panel, and the vote account closed with the escrow\n");
+  });
+
+  it("Step 10f: Reusing a settled escrow id starts with fresh evidence", async () => {
+    console.log("Reopening Escrow 2's id and disputing it again...");
+
+    const escrowAccounts = {
+      escrow: escrow2Pda,
+      buyer: buyer2.publicKey,
+      seller: seller2.publicKey,
+      config: configPda,
+    };
+
+    await program.methods
+      .createEscrow(ESCROW_ID_2, new anchor.BN(LAMPORTS_PER_SOL / 2), [], [], false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer2])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller2])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notDelivered: {} })
+      .accounts({ ...escrowAccounts, party: seller2.publicKey } as any)
+      .signers([seller2])
+      .rpc();
+
+    // Evidence index 0 is taken by the first dispute (Step 7b), the new escrow gets its own
+    const { createdAt } = await program.account.escrow.fetch(escrow2Pda);
+    const evidencePda = evidencePdaFor(escrow2Pda, createdAt, 0);
+    const sellerHash = createHash("sha256").update("delivery-receipt.pdf").digest();
+    await program.methods
+      .submitEvidence([...sellerHash], "ipfs://seller-delivery-receipt", { delivery: {} })
+      .accounts({
+        ...escrowAccounts,
+        evidence: evidencePda,
+        submitter: seller2.publicKey,
+        arbiterAccount: null,
+      } as any)
+      .signers([seller2])
+      .rpc();
+
+    const sellerEvidence = await program.account.evidence.fetch(evidencePda);
+    assert.ok(sellerEvidence.submittedBy.equals(seller2.publicKey));
+    assert.equal(sellerEvidence.index, 0);
+    assert.equal((await program.account.escrow.fetch(escrow2Pda)).evidenceCount, 1);
+
+    // Settle it again so the id is free at the end of the test
+    await program.methods
+      .resolveDispute({ favorSeller: {} })
+      .accounts({ ...escrowAccounts, arbiter: arbiter.publicKey, arbiterAccount: arbiterPda } as any)
+      .signers([arbiter])
+      .rpc();
+    await program.methods
+      .executeRuling()
+      .accounts({
+        ...escrowAccounts,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        feeCollector: feeCollectorPda,
+        buyerReputation: buyer2ReputationPda,
+        sellerReputation: seller2ReputationPda,
+      } as any)
+      .rpc();
+
+    assert.isNull(await program.account.escrow.fetchNullable(escrow2Pda));
+
+    console.log("Reused escrow kept its evidence apart from the first dispute\n");
+  });
+
+  it("Step 10g: Escrow SPL Token and Token-2022 tokens", async () => {
+    const tokenAmount = 1_000_000;
+
+    const tokenPrograms = [
+      { programName: "SPL Token", tokenProgram: TOKEN_PROGRAM_ID, escrowId: 20 },
+      { programName: "Token-2022", tokenProgram: TOKEN_2022_PROGRAM_ID, escrowId: 21 },
+    ];
+
+    for (const { programName, tokenProgram, escrowId } of tokenPrograms) {
+      console.log(`Buyer 2 escrowing ${programName} tokens for Seller 2...`);
+
+      const tokenEscrowId = new anchor.BN(escrowId);
+      const [tokenEscrowPda] = PublicKey.findProgramAddressSync(
+        [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(tokenEscrowId)],
+        program.programId
+      );
+      const [vaultPda] = PublicKey.findProgramAddressSync(
+        [VAULT_SEED, tokenEscrowPda.toBuffer()],
+        program.programId
+      );
+
+      const mint = await createMint(buyer2, tokenProgram);
+      const buyerTokenAccount = await createTokenAccount(buyer2, mint, buyer2.publicKey, tokenProgram);
+      const sellerTokenAccount = await createTokenAccount(buyer2, mint, seller2.publicKey, tokenProgram);
+      const feeCollectorTokenAccount = await createTokenAccount(buyer2, mint, feeCollectorPda, tokenProgram);
+      await mintTokens(buyer2, mint, buyerTokenAccount, tokenProgram, tokenAmount);
+
+      await program.methods
+        .createEscrow(tokenEscrowId, new anchor.BN(tokenAmount), [], [], false)
+        .accounts({
+          escrow: tokenEscrowPda,
+          buyer: buyer2.publicKey,
+          seller: seller2.publicKey,
+          config: configPda,
+          mint,
+          vault: vaultPda,
+          buyerTokenAccount,
+          tokenProgram,
+        } as any)
+        .signers([buyer2])
+        .rpc();
+
+      const escrow = await program.account.escrow.fetch(tokenEscrowPda);
+      assert.ok(escrow.mint.equals(mint));
+      assert.equal(await tokenBalance(vaultPda), tokenAmount);
+      assert.equal(await tokenBalance(buyerTokenAccount), 0);
+
+      await program.methods
+        .acceptEscrow()
+        .accounts({
+          escrow: tokenEscrowPda,
+          seller: seller2.publicKey,
+          buyer: buyer2.publicKey,
+          config: configPda,
+        } as any)
+        .signers([seller2])
+        .rpc();
+
+      await program.methods
+        .releaseFunds()
+        .accounts({
+          escrow: tokenEscrowPda,
+          buyer: buyer2.publicKey,
+          seller: seller2.publicKey,
+          buyerReputation: buyer2ReputationPda,
+          sellerReputation: seller2ReputationPda,
+          config: configPda,
+          feeCollector: feeCollectorPda,
+          mint,
+          vault: vaultPda,
+          sellerTokenAccount,
+          feeCollectorTokenAccount,
+          tokenProgram,
+        } as any)
+        .signers([buyer2])
+        .rpc();
+
+      const expectedFee = (tokenAmount * FEE_BASIS_POINTS) / 10_000;
+      assert.equal(await tokenBalance(sellerTokenAccount), tokenAmount - expectedFee);
+      assert.equal(await tokenBalance(feeCollectorTokenAccount), expectedFee);
+      assert.isNull(await provider.connection.getAccountInfo(vaultPda));
+      assert.isNull(await program.account.escrow.fetchNullable(tokenEscrowPda));
+
+      console.log(`${programName} escrow released, vault and escrow closed\n`);
+    }
+  });
+
+  it("Step 10h: Token-2022 mints with a transfer fee are rejected", async () => {
+    console.log("Buyer 2 trying to escrow tokens that charge a transfer fee...");
+
+    const tokenAmount = 1_000_000;
+    const feeEscrowId = new anchor.BN(22);
+    const [feeEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(feeEscrowId)],
+      program.programId
+    );
+    const [vaultPda] = PublicKey.findProgramAddressSync(
+      [VAULT_SEED, feeEscrowPda.toBuffer()],
+      program.programId
+    );
+
+    // A 1% fee would leave the vault short of the escrow amount
+    const mint = await createMint(buyer2, TOKEN_2022_PROGRAM_ID, 100);
+    const buyerTokenAccount = await createTokenAccount(
+      buyer2,
+      mint,
+      buyer2.publicKey,
+      TOKEN_2022_PROGRAM_ID,
+      extendedSize(TRANSFER_FEE_AMOUNT_LEN)
+    );
+    await mintTokens(buyer2, mint, buyerTokenAccount, TOKEN_2022_PROGRAM_ID, tokenAmount);
+
+    try {
+      await program.methods
+        .createEscrow(feeEscrowId, new anchor.BN(tokenAmount), [], [], false)
+        .accounts({
+          escrow: feeEscrowPda,
+          buyer: buyer2.publicKey,
+          seller: seller2.publicKey,
+          config: configPda,
+          mint,
+          vault: vaultPda,
+          buyerTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+        } as any)
+        .signers([buyer2])
+        .rpc();
+      assert.fail("escrow should reject a mint with a transfer fee");
+    } catch (err) {
+      assert.include(err.toString(), "UnsupportedMint");
+    }
+
+    assert.isNull(await program.account.escrow.fetchNullable(feeEscrowPda));
+    assert.equal(await tokenBalance(buyerTokenAccount), tokenAmount);
+
+    console.log("Transfer fee mint rejected\n");
+  });
+
+  it("Step 10i: A refund of an accepted escrow needs the seller's signature", async () => {
+    console.log("Buyer 2 asking for a refund of an accepted escrow...");
+
+    const refundEscrowId = new anchor.BN(23);
+    const [refundEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(refundEscrowId)],
+      program.programId
+    );
+
+    await program.methods
+      .createEscrow(refundEscrowId, new anchor.BN(LAMPORTS_PER_SOL), [], [], false)
+      .accounts({
+        escrow: refundEscrowPda,
+        buyer: buyer2.publicKey,
+        seller: seller2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer2])
+      .rpc();
+
+    await program.methods
+      .acceptEscrow()
+      .accounts({
+        escrow: refundEscrowPda,
+        seller: seller2.publicKey,
+        buyer: buyer2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([seller2])
+      .rpc();
+
+    const config = await program.account.config.fetch(configPda);
+    const initialBuyerBalance = await provider.connection.getBalance(buyer2.publicKey);
+    const escrowBalance = await provider.connection.getBalance(refundEscrowPda);
+    const initialBuyerRep = await program.account.reputation.fetch(buyer2ReputationPda);
+    const initialSellerRep = await program.account.reputation.fetch(seller2ReputationPda);
+
+    const refundAccounts = {
+      escrow: refundEscrowPda,
+      buyer: buyer2.publicKey,
+      seller: seller2.publicKey,
+      config: configPda,
+      feeCollector: feeCollectorPda,
+      buyerReputation: buyer2ReputationPda,
+      sellerReputation: seller2ReputationPda,
+    };
+
+    // The buyer alone can't take back funds the seller accepted
+    try {
+      await program.methods
+        .refundBuyer()
+        .accounts(refundAccounts as any)
+        .signers([buyer2])
+        .rpc();
+      assert.fail("refund without the seller's signature should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "Missing signature");
+    }
+    assert.deepEqual((await program.account.escrow.fetch(refundEscrowPda)).status, { active: {} });
+
+    await program.methods
+      .refundBuyer()
+      .accounts(refundAccounts as any)
+      .signers([buyer2, seller2])
+      .rpc();
+
+    // The buyer gets the escrow and its rent back, less the buyer-favored fee
+    const expectedFee = (LAMPORTS_PER_SOL * config.buyerDisputeFeeBasisPoints) / 10_000;
+    const finalBuyerBalance = await provider.connection.getBalance(buyer2.publicKey);
+    assert.isNull(await program.account.escrow.fetchNullable(refundEscrowPda));
+    assert.equal(finalBuyerBalance - initialBuyerBalance, escrowBalance - expectedFee);
+
+    // Both record a failed trade, but no dispute
+    const buyerRep = await program.account.reputation.fetch(buyer2ReputationPda);
+    const sellerRep = await program.account.reputation.fetch(seller2ReputationPda);
+    assert.equal(buyerRep.failedTrades.toNumber(), initialBuyerRep.failedTrades.toNumber() + 1);
+    assert.equal(sellerRep.failedTrades.toNumber(), initialSellerRep.failedTrades.toNumber() + 1);
+    assert.equal(sellerRep.disputesLost.toNumber(), initialSellerRep.disputesLost.toNumber());
+
+    console.log(`Seller 2 agreed, Buyer 2 refunded ${(LAMPORTS_PER_SOL - expectedFee) / LAMPORTS_PER_SOL} SOL\n`);
+  });
+
+  it("Step 10j: Disputes close with the dispute window, the seller claims after the timeout", async () => {
+    console.log("Shortening the dispute window and escrow timeout...");
+
+    const windowConfig = (disputeWindow: number, escrowTimeout: number) => ({
+      feeBasisPoints: null,
+      arbiterFeeBasisPoints: null,
+      minArbiterFee: null,
+      buyerDisputeFeeBasisPoints: null,
+      sellerDisputeFeeBasisPoints: null,
+      splitDisputeFeeBasisPoints: null,
+      panelThreshold: null,
+      panelSize: null,
+      votingPeriod: null,
+      appealBond: null,
+      appealWindow: null,
+      minArbiterStake: null,
+      stakeCooldown: null,
+      overturnSlash: null,
+      disputeWindow,
+      escrowTimeout,
+    });
+
+    // The timeout has to outlast the dispute window
+    try {
+      await program.methods
+        .updateConfig(windowConfig(10, 10))
+        .accounts({ config: configPda, admin: admin.publicKey } as any)
+        .signers([admin])
+        .rpc();
+      assert.fail("timeout within the dispute window should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidEscrowTimeout");
+    }
+
+    // A seller with enough successful trades for the first fee tier (Step 1d)
+    const tieredSeller = anchor.web3.Keypair.generate();
+    const [tieredSellerReputationPda] = PublicKey.findProgramAddressSync(
+      [REPUTATION_SEED, tieredSeller.publicKey.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initializeReputation()
+      .accounts({ reputation: tieredSellerReputationPda, user: tieredSeller.publicKey, payer: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+    for (let trade = 1; trade <= 10; trade++) {
+      await program.methods
+        .updateReputation({ successful: {} }, `Trade ${trade} settled before launch`)
+        .accounts({
+          reputation: tieredSellerReputationPda,
+          user: tieredSeller.publicKey,
+          config: configPda,
+          reputationAuthority: null,
+          authority: admin.publicKey,
+        } as any)
+        .signers([admin])
+        .rpc();
+    }
+
+    await program.methods
+      .updateConfig(windowConfig(1, 6))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const timeoutEscrowId = new anchor.BN(24);
+    const [timeoutEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), tieredSeller.publicKey.toBuffer(), escrowIdSeed(timeoutEscrowId)],
+      program.programId
+    );
+
+    await program.methods
+      .createEscrow(timeoutEscrowId, new anchor.BN(LAMPORTS_PER_SOL), [], [], false)
+      .accounts({
+        escrow: timeoutEscrowPda,
+        buyer: buyer2.publicKey,
+        seller: tieredSeller.publicKey,
+        config: configPda,
+      } as any)
+      .signers([buyer2])
+      .rpc();
+
+    await program.methods
+      .acceptEscrow()
+      .accounts({
+        escrow: timeoutEscrowPda,
+        seller: tieredSeller.publicKey,
+        buyer: buyer2.publicKey,
+        config: configPda,
+      } as any)
+      .signers([tieredSeller])
+      .rpc();
+
+    const claimAccounts = {
+      escrow: timeoutEscrowPda,
+      seller: tieredSeller.publicKey,
+      buyer: buyer2.publicKey,
+      buyerReputation: buyer2ReputationPda,
+      sellerReputation: tieredSellerReputationPda,
+      config: configPda,
+      feeCollector: feeCollectorPda,
+    };
+
+    // Too early for the seller to claim
+    try {
+      await program.methods
+        .claimExpiredEscrow()
+        .accounts(claimAccounts as any)
+        .signers([tieredSeller])
+        .rpc();
+      assert.fail("claim before the timeout should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "TimeoutNotReached");
+    }
+
+    // Too late for the buyer to dispute
+    await sleep(3_000);
+    try {
+      await program.methods
+        .raiseDispute({ notDelivered: {} })
+        .accounts({
+          escrow: timeoutEscrowPda,
+          party: buyer2.publicKey,
+          buyer: buyer2.publicKey,
+          seller: tieredSeller.publicKey,
+          config: configPda,
+        } as any)
+        .signers([buyer2])
+        .rpc();
+      assert.fail("dispute after the dispute window should be rejected");
+    } catch (err) {
+      assert.include(err.toString(), "DisputeWindowClosed");
+    }
+
+    // Once the timeout passes the seller is paid, less the platform fee at their tier
+    await sleep(5_000);
+    const initialSellerBalance = await provider.connection.getBalance(tieredSeller.publicKey);
+
+    await program.methods
+      .claimExpiredEscrow()
+      .accounts(claimAccounts as any)
+      .signers([tieredSeller])
+      .rpc();
+
+    const expectedFee = (LAMPORTS_PER_SOL * 200) / 10_000;
+    const finalSellerBalance = await provider.connection.getBalance(tieredSeller.publicKey);
+    assert.equal(finalSellerBalance - initialSellerBalance, LAMPORTS_PER_SOL - expectedFee);
+    assert.isNull(await program.account.escrow.fetchNullable(timeoutEscrowPda));
+
+    // Back to the default 7 day window and 30 day timeout
+    await program.methods
+      .updateConfig(windowConfig(7 * 24 * 60 * 60, 30 * 24 * 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    console.log("Late dispute rejected, seller claimed the timed-out escrow\n");
+  });
+
+  it("Step 10k: Panel members who lose an appeal are charged for it", async () => {
+    console.log("Overturning a panel ruling on appeal...");
+
+    const panelists = [0, 1, 2].map(() => anchor.web3.Keypair.generate());
+    for (const panelist of panelists) {
+      const airdrop = await provider.connection.requestAirdrop(panelist.publicKey, 2 * LAMPORTS_PER_SOL);
+      await provider.connection.confirmTransaction(airdrop);
+      await program.methods
+        .addArbiter()
+        .accounts({ config: configPda, admin: admin.publicKey, arbiter: panelist.publicKey } as any)
+        .signers([admin])
+        .rpc();
+      await program.methods
+        .depositStake(new anchor.BN(MIN_ARBITER_STAKE))
+        .accounts({ arbiter: panelist.publicKey } as any)
+        .signers([panelist])
+        .rpc();
+    }
+    const [voter1, voter2, appealArbiter] = panelists;
+
+    const configUpdate = (panelThreshold: anchor.BN, appealWindow: number) => ({
+      feeBasisPoints: null,
+      arbiterFeeBasisPoints: null,
+      minArbiterFee: null,
+      buyerDisputeFeeBasisPoints: null,
+      sellerDisputeFeeBasisPoints: null,
+      splitDisputeFeeBasisPoints: null,
+      panelThreshold,
+      panelSize: null,
+      votingPeriod: null,
+      appealBond: null,
+      appealWindow,
+      minArbiterStake: null,
+      stakeCooldown: null,
+      overturnSlash: null,
+      disputeWindow: null,
+      escrowTimeout: null,
+    });
+
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(LAMPORTS_PER_SOL), 60 * 60))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const panelEscrowId = new anchor.BN(25);
+    const [panelEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer.publicKey.toBuffer(), seller.publicKey.toBuffer(), escrowIdSeed(panelEscrowId)],
+      program.programId
+    );
+    const [disputeVotePda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("dispute_vote"), panelEscrowPda.toBuffer()],
+      program.programId
+    );
+    const arbiterPdaFor = (key: PublicKey) =>
+      PublicKey.findProgramAddressSync([ARBITER_SEED, key.toBuffer()], program.programId)[0];
+    const escrowAccounts = {
+      escrow: panelEscrowPda,
+      buyer: buyer.publicKey,
+      seller: seller.publicKey,
+      config: configPda,
+    };
+
+    await program.methods
+      .createEscrow(panelEscrowId, new anchor.BN(LAMPORTS_PER_SOL), [], panelists.map((p) => p.publicKey), false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notDelivered: {} })
+      .accounts({ ...escrowAccounts, party: buyer.publicKey } as any)
+      .signers([buyer])
+      .rpc();
+
+    for (const voter of [voter1, voter2]) {
+      await program.methods
+        .castDisputeVote({ favorSeller: {} })
+        .accounts({
+          ...escrowAccounts,
+          disputeVote: disputeVotePda,
+          arbiter: voter.publicKey,
+          arbiterAccount: arbiterPdaFor(voter.publicKey),
+        } as any)
+        .signers([voter])
+        .rpc();
+    }
+
+    const voterAccounts = [voter1, voter2].map((voter) => ({
+      pubkey: arbiterPdaFor(voter.publicKey),
+      isWritable: true,
+      isSigner: false,
+    }));
+    const resolveAccounts = {
+      ...escrowAccounts,
+      arbiter: voter1.publicKey,
+      arbiterAccount: arbiterPdaFor(voter1.publicKey),
+      disputeVote: disputeVotePda,
+    };
+
+    // Every voter's arbiter account has to be passed, in voting order
+    try {
+      await program.methods
+        .resolveDispute({ favorSeller: {} })
+        .accounts(resolveAccounts as any)
+        .remainingAccounts([...voterAccounts].reverse())
+        .signers([voter1])
+        .rpc();
+      assert.fail("Resolving with the voters out of order should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPanelAccounts");
+    }
+    await program.methods
+      .resolveDispute({ favorSeller: {} })
+      .accounts(resolveAccounts as any)
+      .remainingAccounts(voterAccounts)
+      .signers([voter1])
+      .rpc();
+
+    // Buyer 1 appeals to the assigned arbiter who didn't vote, who overturns the panel
+    await program.methods
+      .appealRuling()
+      .accounts({ ...escrowAccounts, appellant: buyer.publicKey, disputeVote: disputeVotePda } as any)
+      .signers([buyer])
+      .rpc();
+    await program.methods
+      .resolveDispute({ favorBuyer: {} })
+      .accounts({
+        ...escrowAccounts,
+        arbiter: appealArbiter.publicKey,
+        arbiterAccount: arbiterPdaFor(appealArbiter.publicKey),
+        disputeVote: disputeVotePda,
+      } as any)
+      .signers([appealArbiter])
+      .rpc();
+
+    const executeAccounts = {
+      ...escrowAccounts,
+      arbiter: appealArbiter.publicKey,
+      arbiterAccount: arbiterPdaFor(appealArbiter.publicKey),
+      disputeVote: disputeVotePda,
+      originalArbiterAccount: arbiterPdaFor(voter1.publicKey),
+      originalStakeVault: PublicKey.findProgramAddressSync(
+        [Buffer.from("stake_vault"), voter1.publicKey.toBuffer()],
+        program.programId
+      )[0],
+      feeCollector: feeCollectorPda,
+      buyerReputation: buyerReputationPda,
+      sellerReputation: sellerReputationPda,
+    };
+    const voterWallets = [voter1, voter2].map((voter) => ({
+      pubkey: voter.publicKey,
+      isWritable: true,
+      isSigner: false,
+    }));
+
+    // The overturned voters' arbiter accounts follow their wallets
+    try {
+      await program.methods
+        .executeRuling()
+        .accounts(executeAccounts as any)
+        .remainingAccounts(voterWallets)
+        .rpc();
+      assert.fail("Overturning a panel without its arbiter accounts should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidPanelAccounts");
+    }
+    await program.methods
+      .executeRuling()
+      .accounts(executeAccounts as any)
+      .remainingAccounts([...voterWallets, ...voterAccounts])
+      .rpc();
+
+    // Both voters are credited with the ruling and charged for losing the appeal
+    for (const voter of [voter1, voter2]) {
+      const stats = await program.account.arbiter.fetch(arbiterPdaFor(voter.publicKey));
+      assert.equal(stats.disputesResolved.toNumber(), 1);
+      assert.equal(stats.favorSellerRulings.toNumber(), 1);
+      assert.equal(stats.appealsLost.toNumber(), 1);
+      assert.equal(stats.pendingRulings, 0);
+    }
+    const appealStats = await program.account.arbiter.fetch(arbiterPdaFor(appealArbiter.publicKey));
+    assert.equal(appealStats.disputesResolved.toNumber(), 1);
+    assert.equal(appealStats.favorBuyerRulings.toNumber(), 1);
+    assert.equal(appealStats.appealsLost.toNumber(), 0);
+    assert.equal(appealStats.pendingRulings, 0);
+    assert.isNull(await program.account.escrow.fetchNullable(panelEscrowPda));
+
+    await program.methods
+      .updateConfig(configUpdate(new anchor.BN(0), 0))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    console.log("Appeal overturned the panel, both voters charged a lost appeal\n");
+  });
+
+  it("Step 10l: A partial split shares the distributable amount by basis points", async () => {
+    console.log("Resolving a dispute with a 30/70 split...");
+
+    const splitEscrowId = new anchor.BN(26);
+    const splitAmount = 1_234_567_891;
+    const [splitEscrowPda] = PublicKey.findProgramAddressSync(
+      [ESCROW_SEED, buyer2.publicKey.toBuffer(), seller2.publicKey.toBuffer(), escrowIdSeed(splitEscrowId)],
+      program.programId
+    );
+    const escrowAccounts = {
+      escrow: splitEscrowPda,
+      buyer: buyer2.publicKey,
+      seller: seller2.publicKey,
+      config: configPda,
+    };
+
+    await program.methods
+      .createEscrow(splitEscrowId, new anchor.BN(splitAmount), [], [], false)
+      .accounts(escrowAccounts as any)
+      .signers([buyer2])
+      .rpc();
+    await program.methods
+      .acceptEscrow()
+      .accounts(escrowAccounts as any)
+      .signers([seller2])
+      .rpc();
+    await program.methods
+      .raiseDispute({ notAsDescribed: {} })
+      .accounts({ ...escrowAccounts, party: buyer2.publicKey } as any)
+      .signers([buyer2])
+      .rpc();
+
+    const resolveAccounts = { ...escrowAccounts, arbiter: arbiter.publicKey, arbiterAccount: arbiterPda };
+
+    // A buyer share above 100% is rejected
+    try {
+      await program.methods
+        .resolveDispute({ partialSplit: { buyerBasisPoints: 10_001 } })
+        .accounts(resolveAccounts as any)
+        .signers([arbiter])
+        .rpc();
+      assert.fail("A split above 10000 basis points should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidSplit");
+    }
+
+    await program.methods
+      .resolveDispute({ partialSplit: { buyerBasisPoints: 3000 } })
+      .accounts(resolveAccounts as any)
+      .signers([arbiter])
+      .rpc();
+
+    const config = await program.account.config.fetch(configPda);
+    const initialSeller2Balance = await provider.connection.getBalance(seller2.publicKey);
+
+    const signature = await program.methods
+      .executeRuling()
+      .accounts({
+        ...escrowAccounts,
+        arbiter: arbiter.publicKey,
+        arbiterAccount: arbiterPda,
+        feeCollector: feeCollectorPda,
+        buyerReputation: buyer2ReputationPda,
+        sellerReputation: seller2ReputationPda,
+      } as any)
+      .rpc({ commitment: "confirmed" });
+
+    // Partial splits pay the split dispute fee, the buyer's share rounds down
+    const arbiterFee = Math.max(
+      Math.floor((splitAmount * ARBITER_FEE_BASIS_POINTS) / 10_000),
+      MIN_ARBITER_FEE
+    );
+    const platformFee = Math.floor((splitAmount * config.splitDisputeFeeBasisPoints) / 10_000);
+    const distributable = splitAmount - arbiterFee - platformFee;
+    const buyerAmount = Math.floor((distributable * 3000) / 10_000);
+
+    const resolved = (await eventsOf(signature)).find((event) => event.name === "disputeResolved");
+    assert.deepEqual(resolved.data.resolution, { partialSplit: { buyerBasisPoints: 3000 } });
+    assert.equal(resolved.data.arbiterFee.toNumber(), arbiterFee);
+    assert.equal(resolved.data.platformFee.toNumber(), platformFee);
+    assert.equal(resolved.data.buyerAmount.toNumber(), buyerAmount);
+    assert.equal(resolved.data.sellerAmount.toNumber(), distributable - buyerAmount);
+    assert.equal(resolved.data.buyerAmount.add(resolved.data.sellerAmount).toNumber(), distributable);
+
+    const finalSeller2Balance = await provider.connection.getBalance(seller2.publicKey);
+    assert.equal(finalSeller2Balance - initialSeller2Balance, distributable - buyerAmount);
+    assert.isNull(await program.account.escrow.fetchNullable(splitEscrowPda));
+
+    console.log(`Split executed: ${buyerAmount} lamports to Buyer 2, ${distributable - buyerAmount} to Seller 2\n`);
+  });
+
+  it("Step 10m: Only the admin and registered authorities adjust reputation", async () => {
+    console.log("Adjusting a reputation manually...");
+
+    const user = anchor.web3.Keypair.generate().publicKey;
+    const authority = anchor.web3.Keypair.generate();
+    const [userReputationPda] = PublicKey.findProgramAddressSync(
+      [REPUTATION_SEED, user.toBuffer()],
+      program.programId
+    );
+    const [reputationAuthorityPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("reputation_authority"), authority.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .initializeReputation()
+      .accounts({ reputation: userReputationPda, user, payer: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    const adjust = (signer: anchor.web3.Keypair, registry: PublicKey | null, update: any, reason: string) =>
+      program.methods
+        .updateReputation(update, reason)
+        .accounts({
+          reputation: userReputationPda,
+          user,
+          config: configPda,
+          reputationAuthority: registry,
+          authority: signer.publicKey,
+        } as any)
+        .signers([signer])
+        .rpc({ commitment: "confirmed" });
+    const adjustedEvent = async (signature: string) =>
+      (await eventsOf(signature)).find((event) => event.name === "reputationAdjusted").data;
+
+    // An unregistered signer can't adjust anyone's reputation
+    try {
+      await adjust(authority, null, { failed: {} }, "Not my call");
+      assert.fail("An unregistered authority should fail");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    // The admin can, with a reason
+    try {
+      await adjust(admin, null, { successful: {} }, "");
+      assert.fail("An adjustment without a reason should fail");
+    } catch (err) {
+      assert.include(err.toString(), "InvalidReason");
+    }
+    const adminAdjusted = await adjustedEvent(
+      await adjust(admin, null, { successful: {} }, "Off-chain trade verified")
+    );
+    assert.ok(adminAdjusted.user.equals(user));
+    assert.ok(adminAdjusted.authority.equals(admin.publicKey));
+    assert.deepEqual(adminAdjusted.update, { successful: {} });
+    assert.equal(adminAdjusted.reason, "Off-chain trade verified");
+
+    // So can an authority once the admin registers it
+    await program.methods
+      .addReputationAuthority()
+      .accounts({
+        config: configPda,
+        reputationAuthority: reputationAuthorityPda,
+        authority: authority.publicKey,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+    const authorityAdjusted = await adjustedEvent(
+      await adjust(authority, reputationAuthorityPda, { failed: {} }, "Chargeback confirmed")
+    );
+    assert.ok(authorityAdjusted.authority.equals(authority.publicKey));
+    assert.deepEqual(authorityAdjusted.update, { failed: {} });
+    assert.equal(authorityAdjusted.reason, "Chargeback confirmed");
+
+    const adjusted = await program.account.reputation.fetch(userReputationPda);
+    assert.equal(adjusted.successfulTrades.toNumber(), 1);
+    assert.equal(adjusted.failedTrades.toNumber(), 1);
+
+    // Revoking the authority takes the right away again
+    await program.methods
+      .removeReputationAuthority()
+      .accounts({
+        config: configPda,
+        reputationAuthority: reputationAuthorityPda,
+        authority: authority.publicKey,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+    try {
+      await adjust(authority, null, { failed: {} }, "Chargeback confirmed");
+      assert.fail("A revoked authority should fail");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    console.log("Admin and registered authority adjusted the reputation, others were rejected\n");
+  });
+
+  it("Step 11: Remove arbiter from platform", async () => {
+    console.log("Admin removing arbiter...");
+
+    await program.methods
+      .removeArbiter()
+      .accounts({
+        config: configPda,
+        admin: admin.publicKey,
+        arbiter: arbiter.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+
+    const arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
+
+    assert.isFalse(arbiterAccount.isActive);
+
+    console.log("Arbiter deactivated\n");
+  });
+
+  it("Step 11b: Rotate the admin with a two-step transfer", async () => {
+    console.log("Transferring admin rights...");
+
+    const newAdmin = anchor.web3.Keypair.generate();
+
+    // A proposal can be withdrawn before it is accepted
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .cancelAdminTransfer()
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    let config = await program.account.config.fetch(configPda);
+    assert.isNull(config.pendingAdmin);
+
+    // Leave the guardian role with the admin, so it moves along with the handover
+    await program.methods
+      .setGuardian(admin.publicKey)
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    // Hand over to the new admin, who must sign to accept
+    await program.methods
+      .proposeAdmin(newAdmin.publicKey)
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, newAdmin: newAdmin.publicKey } as any)
+      .signers([newAdmin])
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
+    assert.ok(config.admin.equals(newAdmin.publicKey));
+    assert.ok(config.guardian.equals(newAdmin.publicKey));
+    assert.isNull(config.pendingAdmin);
+
+    // The previous admin's key lost the guardian role too
+    try {
+      await program.methods
+        .setPause(true, true)
+        .accounts({ config: configPda, authority: admin.publicKey } as any)
+        .signers([admin])
+        .rpc();
+      assert.fail("previous admin should not be able to pause");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    // And back again, so the original admin holds the config at the end
+    await program.methods
+      .proposeAdmin(admin.publicKey)
+      .accounts({ config: configPda, admin: newAdmin.publicKey } as any)
+      .signers([newAdmin])
+      .rpc();
+    await program.methods
+      .acceptAdmin()
+      .accounts({ config: configPda, newAdmin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    config = await program.account.config.fetch(configPda);
+    assert.ok(config.guardian.equals(admin.publicKey));
+
+    console.log("Admin rotated and restored\n");
+  });
+
+  it("Step 11c: Admin slashes the removed arbiter, who then unstakes", async () => {
+    console.log("Slashing and withdrawing arbiter stake...");
+
+    const slashAmount = 0.1 * LAMPORTS_PER_SOL;
+    const initialFeeCollectorBalance = await provider.connection.getBalance(feeCollectorPda);
+
+    await program.methods
+      .slashArbiter(new anchor.BN(slashAmount), "Ruled on a dispute with an undisclosed conflict")
+      .accounts({
+        config: configPda,
+        arbiter: arbiter.publicKey,
+        recipient: feeCollectorPda,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+
+    let arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
+    const finalFeeCollectorBalance = await provider.connection.getBalance(feeCollectorPda);
+    assert.equal(arbiterAccount.stake.toNumber(), MIN_ARBITER_STAKE - slashAmount);
+    assert.equal(finalFeeCollectorBalance - initialFeeCollectorBalance, slashAmount);
+
+    // A slash that would leave the vault below rent exemption stops at the rent reserve
+    const rentReserve = await provider.connection.getMinimumBalanceForRentExemption(0);
+    await program.methods
+      .slashArbiter(new anchor.BN(MIN_ARBITER_STAKE - slashAmount - 1_000), "Repeated the conflict")
+      .accounts({
+        config: configPda,
+        arbiter: arbiter.publicKey,
+        recipient: feeCollectorPda,
+        admin: admin.publicKey,
+      } as any)
+      .signers([admin])
+      .rpc();
+
+    arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
+    assert.equal(arbiterAccount.stake.toNumber(), rentReserve);
+    assert.equal(
+      await provider.connection.getBalance(feeCollectorPda),
+      finalFeeCollectorBalance + MIN_ARBITER_STAKE - slashAmount - rentReserve
+    );
+
+    // Skip the cooldown so the remaining stake can be withdrawn right away
+    await program.methods
+      .updateConfig({
+        feeBasisPoints: null,
+        arbiterFeeBasisPoints: null,
+        minArbiterFee: null,
+        buyerDisputeFeeBasisPoints: null,
+        sellerDisputeFeeBasisPoints: null,
+        splitDisputeFeeBasisPoints: null,
+        panelThreshold: null,
+        panelSize: null,
+        votingPeriod: null,
+        appealBond: null,
+        appealWindow: null,
+        minArbiterStake: null,
+        stakeCooldown: 0,
+        overturnSlash: null,
+        disputeWindow: null,
+        escrowTimeout: null,
+      })
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .requestUnstake()
+      .accounts({ config: configPda, arbiter: arbiter.publicKey } as any)
+      .signers([arbiter])
+      .rpc();
+
+    // Lamports sent straight to the vault are withdrawn with the stake instead of stranding it
+    const [stakeVaultPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_vault"), arbiter.publicKey.toBuffer()],
+      program.programId
+    );
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: stakeVaultPda,
+          lamports: 1,
+        })
+      )
+    );
+
+    const initialArbiterBalance = await provider.connection.getBalance(arbiter.publicKey);
+
+    await program.methods
+      .withdrawStake()
+      .accounts({ config: configPda, arbiter: arbiter.publicKey } as any)
+      .signers([arbiter])
+      .rpc();
+
+    arbiterAccount = await program.account.arbiter.fetch(arbiterPda);
+    const finalArbiterBalance = await provider.connection.getBalance(arbiter.publicKey);
+    assert.equal(arbiterAccount.stake.toNumber(), 0);
+    assert.equal(finalArbiterBalance - initialArbiterBalance, rentReserve + 1);
+    assert.equal(await provider.connection.getBalance(stakeVaultPda), 0);
+
+    console.log(`Arbiter slashed twice and withdrew the remaining ${rentReserve + 1} lamports\n`);
+  });
+
+  it("Step 11d: Reactivate the arbiter, then close its account", async () => {
+    console.log("Reactivating and closing the arbiter...");
+
+    const arbiterAccounts = {
+      config: configPda,
+      arbiter: arbiter.publicKey,
+      admin: admin.publicKey,
+    };
+
+    await program.methods
+      .reactivateArbiter()
+      .accounts(arbiterAccounts as any)
+      .signers([admin])
+      .rpc();
+
+    const reactivated = await program.account.arbiter.fetch(arbiterPda);
+    assert.isTrue(reactivated.isActive);
+    assert.equal(reactivated.pendingRulings, 0);
+
+    // Closing requires a removed, unstaked arbiter without pending rulings
+    await program.methods
+      .removeArbiter()
+      .accounts(arbiterAccounts as any)
+      .signers([admin])
+      .rpc();
+
+    const initialAdminBalance = await provider.connection.getBalance(admin.publicKey);
+
+    await program.methods
+      .closeArbiter()
+      .accounts(arbiterAccounts as any)
+      .signers([admin])
+      .rpc();
+
+    const finalAdminBalance = await provider.connection.getBalance(admin.publicKey);
+    assert.isNull(await program.account.arbiter.fetchNullable(arbiterPda));
+    assert.isTrue(finalAdminBalance > initialAdminBalance);
+
+    console.log("Arbiter reactivated, removed again and closed\n");
+  });
+
+  it("Step 11e: Migrate an arbiter account created with the old layout", async () => {
+    console.log("Migrating a pre-staking arbiter account...");
+
+    // Loaded into the validator from tests/fixtures/legacy_arbiter.json
+    const legacyArbiter = new PublicKey(Buffer.alloc(32, 8));
+    const [legacyArbiterPda] = PublicKey.findProgramAddressSync(
+      [ARBITER_SEED, legacyArbiter.toBuffer()],
+      program.programId
+    );
+    const [, stakeVaultBump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("stake_vault"), legacyArbiter.toBuffer()],
+      program.programId
+    );
+    const arbiterAccounts = {
+      config: configPda,
+      arbiter: legacyArbiter,
+      admin: admin.publicKey,
+    };
+
+    const legacyAccount = await provider.connection.getAccountInfo(legacyArbiterPda);
+    assert.equal(legacyAccount.data.length, 90);
+
+    // The old layout can't be managed until it is migrated
+    try {
+      await program.methods
+        .removeArbiter()
+        .accounts(arbiterAccounts as any)
+        .signers([admin])
+        .rpc();
+      assert.fail("Removing an unmigrated arbiter should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AccountDidNotDeserialize");
+    }
+
+    const migrateAccounts = {
+      arbiterAccount: legacyArbiterPda,
+      arbiter: legacyArbiter,
+      payer: admin.publicKey,
+    };
+
+    await program.methods
+      .migrateArbiter()
+      .accounts(migrateAccounts as any)
+      .signers([admin])
+      .rpc();
+
+    const migratedAccount = await provider.connection.getAccountInfo(legacyArbiterPda);
+    const newSize = program.account.arbiter.size;
+    assert.equal(migratedAccount.data.length, newSize);
+    assert.isTrue(
+      migratedAccount.lamports >= (await provider.connection.getMinimumBalanceForRentExemption(newSize))
+    );
+
+    // Status and history survive, staking and statistics start at zero
+    const migrated = await program.account.arbiter.fetch(legacyArbiterPda);
+    assert.ok(migrated.arbiter.equals(legacyArbiter));
+    assert.ok(migrated.addedBy.equals(new PublicKey(Buffer.alloc(32, 9))));
+    assert.equal(migrated.addedAt.toNumber(), 1_700_000_000);
+    assert.isTrue(migrated.isActive);
+    assert.equal(migrated.stake.toNumber(), 0);
+    assert.equal(migrated.disputesResolved.toNumber(), 0);
+    assert.equal(migrated.stakeVaultBump, stakeVaultBump);
+
+    // A second migration has nothing to do
+    try {
+      await program.methods
+        .migrateArbiter()
+        .accounts(migrateAccounts as any)
+        .signers([admin])
+        .rpc();
+      assert.fail("Migrating twice should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyMigrated");
+    }
+
+    // The migrated arbiter can now be removed and closed like any other
+    await program.methods
+      .removeArbiter()
+      .accounts(arbiterAccounts as any)
+      .signers([admin])
+      .rpc();
+    await program.methods
+      .closeArbiter()
+      .accounts(arbiterAccounts as any)
+      .signers([admin])
+      .rpc();
+    assert.isNull(await program.account.arbiter.fetchNullable(legacyArbiterPda));
+
+    console.log(`Arbiter account grew from ${legacyAccount.data.length} to ${migratedAccount.data.length} bytes\n`);
+  });
+
+  it("Step 12: Verify final system state", async () => {
+    console.log("Verifying final system state...");
+
+    // Check config
+    const config = await program.account.config.fetch(configPda);
+    assert.ok(config.admin.equals(admin.publicKey));
+    assert.equal(config.feeBasisPoints, FEE_BASIS_POINTS);
+
+    // Check settled escrows were closed
+    const escrow1 = await program.account.escrow.fetchNullable(escrowPda);
+    const escrow2 = await program.account.escrow.fetchNullable(escrow2Pda);
+    assert.isNull(escrow1);
+    assert.isNull(escrow2);
 
     // Check reputation accounts
     const buyerRep = await program.account.reputation.fetch(buyerReputationPda);
@@ -0,0 +1,81 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Escrow } from "../../target/types/escrow";
+import { PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { assert } from "chai";
+
+describe("Config Migration", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.escrow as Program<Escrow>;
+
+  // Matches tests/fixtures/legacy_config.json, a config saved with the original layout
+  const legacyAdmin = anchor.web3.Keypair.fromSeed(Buffer.alloc(32, 9));
+  const LEGACY_FEE_BASIS_POINTS = 250;
+  const LEGACY_CONFIG_SIZE = 52;
+  const ARBITER_FEE_BASIS_POINTS = 100;
+  const MIN_ARBITER_FEE = 0.01 * LAMPORTS_PER_SOL;
+
+  const [configPda, configBump] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+  const [, feeCollectorBump] = PublicKey.findProgramAddressSync(
+    [Buffer.from("fee_collector")],
+    program.programId
+  );
+
+  const migrate = (admin: anchor.web3.Keypair) =>
+    program.methods
+      .migrateConfig(ARBITER_FEE_BASIS_POINTS, new anchor.BN(MIN_ARBITER_FEE))
+      .accounts({ config: configPda, admin: admin.publicKey } as any)
+      .signers([admin])
+      .rpc();
+
+  before(async () => {
+    const airdrop = await provider.connection.requestAirdrop(legacyAdmin.publicKey, LAMPORTS_PER_SOL);
+    await provider.connection.confirmTransaction(airdrop);
+  });
+
+  it("Step 1: Only the recorded admin can migrate it", async () => {
+    try {
+      await migrate(anchor.web3.Keypair.generate());
+      assert.fail("Migrating as another signer should fail");
+    } catch (err) {
+      assert.include(err.toString(), "Unauthorized");
+    }
+
+    const legacy = await provider.connection.getAccountInfo(configPda);
+    assert.equal(legacy.data.length, LEGACY_CONFIG_SIZE);
+  });
+
+  it("Step 2: The admin migrates it, keeping the admin, fee and bumps", async () => {
+    await migrate(legacyAdmin);
+
+    const migrated = await provider.connection.getAccountInfo(configPda);
+    assert.isAbove(migrated.data.length, LEGACY_CONFIG_SIZE);
+    assert.equal(
+      migrated.lamports,
+      await provider.connection.getMinimumBalanceForRentExemption(migrated.data.length)
+    );
+
+    const config = await program.account.config.fetch(configPda);
+    assert.ok(config.admin.equals(legacyAdmin.publicKey));
+    assert.equal(config.feeBasisPoints, LEGACY_FEE_BASIS_POINTS);
+    assert.equal(config.bump, configBump);
+    assert.equal(config.feeCollectorBump, feeCollectorBump);
+    assert.equal(config.arbiterFeeBasisPoints, ARBITER_FEE_BASIS_POINTS);
+    assert.equal(config.minArbiterFee.toNumber(), MIN_ARBITER_FEE);
+    assert.isNull(config.pendingAdmin);
+  });
+
+  it("Step 3: A migrated config can't be migrated again", async () => {
+    try {
+      await migrate(legacyAdmin);
+      assert.fail("Migrating twice should fail");
+    } catch (err) {
+      assert.include(err.toString(), "AlreadyMigrated");
+    }
+  });
+});